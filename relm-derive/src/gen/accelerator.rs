@@ -0,0 +1,113 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Parsing and code generation for the `accelerators!` macro, which declares the keyboard
+//! accelerators of a widget, like:
+//!
+//! ```ignore
+//! accelerators! {
+//!     /// Save the file
+//!     "<Control>s" => Save,
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Attribute, Expr, Lit, LitStr, Meta, MetaNameValue, Token};
+use syn::parse::{Parse, ParseStream, Result};
+
+#[derive(Debug)]
+pub struct AcceleratorList {
+    accelerators: Vec<Accelerator>,
+}
+
+#[derive(Debug)]
+struct Accelerator {
+    accelerator: LitStr,
+    description: Option<String>,
+    msg: Expr,
+}
+
+impl Parse for AcceleratorList {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut accelerators = vec![];
+        while !input.is_empty() {
+            accelerators.push(input.parse()?);
+            if !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+            }
+        }
+        Ok(AcceleratorList {
+            accelerators,
+        })
+    }
+}
+
+impl Parse for Accelerator {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
+        let accelerator = input.parse()?;
+        let _: Token![=>] = input.parse()?;
+        let msg = input.parse()?;
+        Ok(Accelerator {
+            accelerator,
+            description: doc_comment(&attributes),
+            msg,
+        })
+    }
+}
+
+/// Get the text of the doc comments, which describe an accelerator.
+fn doc_comment(attributes: &[Attribute]) -> Option<String> {
+    let lines: Vec<_> = attributes.iter()
+        .filter_map(|attribute| match attribute.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Str(ref text), .. })) if path.is_ident("doc") =>
+                Some(text.value().trim().to_string()),
+            _ => None,
+        })
+        .collect();
+    if lines.is_empty() {
+        None
+    }
+    else {
+        Some(lines.join(" "))
+    }
+}
+
+/// Generate the code creating the accelerators in the `view()` function, for the `root` widget.
+pub fn gen_accelerators(accelerators: &AcceleratorList, root: TokenStream) -> TokenStream {
+    let adds = accelerators.accelerators.iter().map(|accelerator| {
+        let msg = &accelerator.msg;
+        let description =
+            match accelerator.description {
+                Some(ref description) => quote! { ::std::option::Option::Some(#description) },
+                None => quote! { ::std::option::Option::None },
+            };
+        let accelerator = &accelerator.accelerator;
+        quote_spanned! { accelerator.span() =>
+            __relm_accelerators.add(#accelerator, #description, move || #msg);
+        }
+    });
+    quote! {
+        let __relm_accelerators = ::relm::Accelerators::new(&#root, ::std::clone::Clone::clone(relm.stream()));
+        #(#adds)*
+    }
+}
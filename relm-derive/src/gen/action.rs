@@ -0,0 +1,215 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Parsing and code generation for the `actions!` macro, which declares the actions of a widget,
+//! like:
+//!
+//! ```ignore
+//! actions! {
+//!     prefix: "win",
+//!     quit => Quit,
+//!     open(String) => Open,
+//!     dark_theme(bool) = self.model.dark_theme => DarkTheme,
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Expr, Ident, LitStr, Token, Type, parenthesized};
+use syn::fold::Fold;
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+use super::ActionModelMap;
+use super::adder::{ActionBinding, ActionProperty};
+use super::transformer::Transformer;
+use super::walker::ModelVariableVisitor;
+use super::MODEL_IDENT;
+
+const DEFAULT_PREFIX: &str = "win";
+
+#[derive(Debug)]
+pub struct ActionList {
+    actions: Vec<Action>,
+    // The actions whose enabled state depends on the model.
+    enabled: Vec<(LitStr, Expr)>,
+    prefix: LitStr,
+}
+
+#[derive(Debug)]
+struct Action {
+    msg: Expr,
+    name: LitStr,
+    parameter_type: Option<Type>,
+    state: Option<Expr>,
+}
+
+impl ActionList {
+    /// Add an action without parameter emitting `msg`, like the ones of the menu items.
+    pub fn add(&mut self, name: LitStr, msg: Expr) {
+        self.actions.push(Action {
+            msg,
+            name,
+            parameter_type: None,
+            state: None,
+        });
+    }
+
+    /// Enable the action `name` only when `expr` is true.
+    pub fn bind_enabled(&mut self, name: LitStr, expr: Expr) {
+        self.enabled.push((name, expr));
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.actions.iter().any(|action| action.name.value() == name)
+    }
+
+    pub fn prefix(&self) -> String {
+        self.prefix.value()
+    }
+}
+
+impl Default for ActionList {
+    fn default() -> Self {
+        ActionList {
+            actions: vec![],
+            enabled: vec![],
+            prefix: LitStr::new(DEFAULT_PREFIX, proc_macro2::Span::call_site()),
+        }
+    }
+}
+
+impl Parse for ActionList {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut list = ActionList::default();
+        if input.peek(Ident) && input.peek2(Token![:]) {
+            let ident: Ident = input.parse()?;
+            if ident != "prefix" {
+                return Err(Error::new(ident.span(), "expected `prefix` or an action"));
+            }
+            let _: Token![:] = input.parse()?;
+            list.prefix = input.parse()?;
+            if !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+            }
+        }
+        while !input.is_empty() {
+            list.actions.push(input.parse()?);
+            if !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+            }
+        }
+        Ok(list)
+    }
+}
+
+impl Parse for Action {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+        let parameter_type =
+            if input.peek(syn::token::Paren) {
+                let content;
+                parenthesized!(content in input);
+                Some(content.parse()?)
+            }
+            else {
+                None
+            };
+        let state =
+            if input.peek(Token![=]) && !input.peek(Token![=>]) {
+                let token: Token![=] = input.parse()?;
+                if parameter_type.is_none() {
+                    return Err(Error::new(token.span(),
+                        "a stateful action needs the type of its state, like `name(bool) = self.model.field`"));
+                }
+                Some(input.parse()?)
+            }
+            else {
+                None
+            };
+        let _: Token![=>] = input.parse()?;
+        let msg = input.parse()?;
+        Ok(Action {
+            msg,
+            name: LitStr::new(&name.to_string(), name.span()),
+            parameter_type,
+            state,
+        })
+    }
+}
+
+/// Generate the code creating the actions in the `view()` function, for the `root` widget.
+pub fn gen_actions(actions: &ActionList, root: TokenStream) -> TokenStream {
+    let prefix = &actions.prefix;
+    let adds = actions.actions.iter().map(|action| {
+        let name = &action.name;
+        let msg = &action.msg;
+        match (&action.parameter_type, &action.state) {
+            (None, _) => quote_spanned! { name.span() =>
+                __relm_actions.add(#name, move || #msg);
+            },
+            (Some(typ), None) => quote_spanned! { name.span() =>
+                __relm_actions.add_with_parameter::<#typ, _>(#name, #msg);
+            },
+            (Some(typ), Some(state)) => {
+                let mut remover = Transformer::new(MODEL_IDENT);
+                let state = remover.fold_expr(state.clone());
+                quote_spanned! { name.span() =>
+                    __relm_actions.add_stateful::<#typ, _>(#name, &(#state), #msg);
+                }
+            },
+        }
+    });
+    let enabled = actions.enabled.iter().map(|(name, expr)| {
+        let mut remover = Transformer::new(MODEL_IDENT);
+        let expr = remover.fold_expr(expr.clone());
+        quote_spanned! { name.span() =>
+            __relm_actions.set_enabled(#name, #expr);
+        }
+    });
+    quote! {
+        let __relm_actions = ::relm::Actions::new(&#root, #prefix, ::std::clone::Clone::clone(relm.stream()));
+        #(#adds)*
+        #(#enabled)*
+    }
+}
+
+/// Map the attributes of the model to the states and the enabled states of the actions that
+/// depend on them.
+pub fn get_actions_model_map(actions: &ActionList, map: &mut ActionModelMap) {
+    let states = actions.actions.iter()
+        .filter_map(|action| action.state.as_ref().map(|state| (&action.name, state, ActionProperty::State)));
+    let enabled = actions.enabled.iter()
+        .map(|(name, expr)| (name, expr, ActionProperty::Enabled));
+    for (name, expr, property) in states.chain(enabled) {
+        let mut visitor = ModelVariableVisitor::new();
+        visitor.visit_expr(expr);
+        for var in visitor.idents {
+            let set = map.entry(var).or_default();
+            set.insert(ActionBinding {
+                expr: expr.clone(),
+                name: name.clone(),
+                property,
+            });
+        }
+    }
+}
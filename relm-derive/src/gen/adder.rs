@@ -20,84 +20,337 @@
  */
 
 //! The adder adds the calls to set_property() or emit(Msg) whenever we assign to an attribute of
-//! the model.
+//! the model or mutate it in place (by calling a mutating method or borrowing it mutably).
+//!
+//! In the methods whose widgets are updated at the end, like `update()`, the analysis is
+//! conservative: every method call on an attribute, `&mut` borrow of it or `ref mut` binding to it
+//! marks it as dirty, since marking an attribute which was only read merely updates its widgets
+//! again.
 
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned, TokenStreamExt};
 use syn::{
+    BinOp,
     Expr,
     ExprAssign,
     ExprAssignOp,
+    ExprBinary,
     ExprBlock,
+    ExprCall,
     ExprField,
+    ExprForLoop,
+    ExprIf,
+    ExprIndex,
+    ExprLet,
+    ExprMatch,
+    ExprMethodCall,
+    ExprParen,
     ExprPath,
+    ExprReference,
+    ExprUnary,
+    ExprWhile,
+    Block as SynBlock,
     Ident,
+    Local,
+    LitStr,
+    Pat,
+    PatIdent,
     Path,
     Stmt,
+    Token,
+    UnOp,
     parse,
 };
-use syn::Expr::{Assign, AssignOp, Block, Field};
-use syn::fold::{Fold, fold_expr};
+use syn::Expr::{Assign, AssignOp, Block, Call, Field, ForLoop, If, Index, Let, Match, MethodCall, Paren, Reference, Unary};
+use syn::fold::{Fold, fold_block, fold_expr};
 use syn::Member::Named;
+use syn::punctuated::Punctuated;
+use syn::visit::{Visit, visit_expr, visit_pat_ident};
+
+use super::ModelMaps;
+use super::generator::{gen_build_condition_ident, gen_condition_index_ident, gen_sync_loop_ident};
 
-use super::{MsgModelMap, PropertyModelMap};
+/// The methods of the standard collections, `String`, `Option` and `Cell` that mutate their
+/// receiver and return an owned value.
+/// They are used where the widgets are updated right after the mutation and to detect the message
+/// cycles, where marking the attributes which are only read is not an option.
+const MUTATING_METHODS: &[&str] = &[
+    "append", "clear", "dedup", "dedup_by", "dedup_by_key", "extend", "extend_from_slice", "fill", "insert",
+    "insert_str", "pop", "pop_back", "pop_front", "push", "push_back", "push_front", "push_str", "remove",
+    "replace", "resize", "retain", "reverse", "set", "shrink_to_fit", "sort", "sort_by", "sort_by_key",
+    "sort_unstable", "sort_unstable_by", "sort_unstable_by_key", "split_off", "swap", "swap_remove", "take",
+    "truncate",
+];
 
 pub struct Adder<'a> {
-    msg_map: &'a MsgModelMap,
-    property_map: &'a PropertyModelMap,
+    // Whether the attributes are marked as dirty, to update the widgets in the flush method,
+    // instead of updating the widgets right after the assignment.
+    batch: bool,
+    maps: &'a ModelMaps,
+    pub marked_dirty: bool,
 }
 
 impl<'a> Adder<'a> {
-    pub fn new(property_map: &'a PropertyModelMap, msg_map: &'a MsgModelMap) -> Self {
+    pub fn new(maps: &'a ModelMaps, batch: bool) -> Self {
         Adder {
-            msg_map,
-            property_map,
+            batch,
+            maps,
+            marked_dirty: false,
         }
     }
-}
 
-impl<'a> Adder<'a> {
-    fn fold_assign(&self, lhs: Expr, mut new_assign: Expr) -> Expr {
-        let mut statements = vec![];
-        let new_statements =
-            if let Field(ExprField { ref base, member: Named(ref ident), .. }) = lhs {
-                if is_model_path(base) {
-                    Some(create_stmts(ident, self.property_map, self.msg_map))
-                }
-                else {
-                    None
-                }
+    fn create_stmts(&mut self, ident: &Ident) -> Vec<Stmt> {
+        if self.batch {
+            if self.maps.is_bound(ident) {
+                self.marked_dirty = true;
+                vec![parse_stmt(quote_spanned! { ident.span() => {
+                    self.dirty.#ident.set(true);
+                }})]
             }
             else {
-                None
+                vec![]
+            }
+        }
+        else {
+            create_stmts(ident, self.maps)
+        }
+    }
+
+    /// Get the attributes of the model borrowed by the place expression `expr`: all of them when
+    /// this is the model itself, since one of its methods can mutate any of them.
+    fn borrowed_fields(&self, expr: &Expr) -> Vec<Ident> {
+        match *expr {
+            Paren(ExprParen { ref expr, .. }) => self.borrowed_fields(expr),
+            _ if is_model_path(expr) => self.maps.fields().into_iter().cloned().collect(),
+            _ => model_field(expr).cloned().into_iter().collect(),
+        }
+    }
+
+    /// Mark the attributes as dirty before evaluating `expr`.
+    /// The value is taken from a tuple, instead of a block, for its temporaries to live as long
+    /// as in the original expression.
+    fn mark_dirty(&mut self, fields: &[Ident], expr: Expr) -> Expr {
+        let stmts: Vec<_> = fields.iter()
+            .flat_map(|ident| self.create_stmts(ident))
+            .collect();
+        if stmts.is_empty() {
+            return expr;
+        }
+        parse(quote! {
+            ({ #(#stmts)* }, #expr).1
+        }.into()).expect("marked expression")
+    }
+
+    /// Conservatively find the attributes of the model which an expression can mutate.
+    fn fold_expr_batch(&mut self, expr: Expr) -> Expr {
+        let fields =
+            match expr {
+                Assign(ExprAssign { ref left, .. }) | AssignOp(ExprAssignOp { ref left, .. }) => {
+                    let fields: Vec<_> = model_field(left).cloned().into_iter().collect();
+                    let new_expr = fold_expr(self, expr);
+                    return self.fold_assign(&fields, new_expr);
+                },
+                // The receiver can be borrowed mutably by an autoref.
+                MethodCall(ExprMethodCall { ref receiver, .. }) => self.borrowed_fields(receiver),
+                Reference(ExprReference { mutability: Some(_), ref expr, .. }) => self.borrowed_fields(expr),
+                Match(ExprMatch { expr: ref scrutinee, ref arms, .. }) if arms.iter().any(|arm| has_ref_mut(&arm.pat)) =>
+                    self.borrowed_fields(scrutinee),
+                If(ExprIf { ref cond, .. }) => {
+                    match **cond {
+                        Let(ExprLet { ref pat, ref expr, .. }) if has_ref_mut(pat) => self.borrowed_fields(expr),
+                        _ => return fold_expr(self, expr),
+                    }
+                },
+                _ => return fold_expr(self, expr),
             };
-        if let Some(mut stmts) = new_statements {
+        let new_expr = fold_expr(self, expr);
+        self.mark_dirty(&fields, new_expr)
+    }
+
+    /// Add the statements after an expression whose value is `()`, like an assignment or a loop.
+    fn fold_assign(&mut self, fields: &[Ident], mut new_assign: Expr) -> Expr {
+        let mut statements = vec![];
+        let mut new_statements = vec![];
+        for ident in fields {
+            new_statements.append(&mut self.create_stmts(ident));
+        }
+        if !new_statements.is_empty() {
             let statement: Stmt = parse(quote! {
                 #new_assign;
             }.into()).expect("expression statement");
             statements.push(statement);
-            statements.append(&mut stmts);
+            statements.append(&mut new_statements);
             new_assign = parse(quote! {{
                 #(#statements)*
             }}.into()).expect("statements");
         }
         new_assign
     }
+
+    /// Add the statements after an expression mutating attributes of the model in place while
+    /// keeping the value of this expression.
+    fn fold_mutation(&mut self, fields: &[Ident], new_expr: Expr) -> Expr {
+        let mut stmts = vec![];
+        for ident in fields {
+            stmts.append(&mut self.create_stmts(ident));
+        }
+        if stmts.is_empty() {
+            return new_expr;
+        }
+        parse(quote! {{
+            #[allow(clippy::let_unit_value)]
+            let __relm_result = #new_expr;
+            #(#stmts)*
+            __relm_result
+        }}.into()).expect("statements")
+    }
 }
 
 impl<'a> Fold for Adder<'a> {
+    fn fold_block(&mut self, block: SynBlock) -> SynBlock {
+        let mut block = fold_block(self, block);
+        if self.batch {
+            // Mark the attributes bound with `let Some(ref mut value) = self.model.value;` before
+            // the statement, since the place expression cannot be wrapped.
+            let mut stmts = vec![];
+            for stmt in block.stmts {
+                if let Stmt::Local(Local { ref pat, init: Some((_, ref init)), .. }) = stmt {
+                    if has_ref_mut(pat) {
+                        let fields = self.borrowed_fields(init);
+                        for field in fields {
+                            stmts.extend(self.create_stmts(&field));
+                        }
+                    }
+                }
+                stmts.push(stmt);
+            }
+            block.stmts = stmts;
+        }
+        block
+    }
+
     fn fold_expr(&mut self, expr: Expr) -> Expr {
-        let lhs_clone =
+        if self.batch {
+            return self.fold_expr_batch(expr);
+        }
+        let mutated_fields =
             match expr {
-                Assign(ExprAssign { ref left, .. }) | AssignOp(ExprAssignOp { ref left, .. }) => *left.clone(),
+                Assign(ExprAssign { ref left, .. }) | AssignOp(ExprAssignOp { ref left, .. }) => {
+                    let fields: Vec<_> = model_field(left).cloned().into_iter().collect();
+                    let new_expr = fold_expr(self, expr);
+                    return self.fold_assign(&fields, new_expr);
+                },
+                Call(ExprCall { ref args, .. }) => mut_ref_args(args),
+                ForLoop(ExprForLoop { expr: ref iterable, .. }) => {
+                    let fields: Vec<_> = mut_borrowed_field(iterable).into_iter().collect();
+                    let new_expr = fold_expr(self, expr);
+                    return self.fold_assign(&fields, new_expr);
+                },
+                MethodCall(ExprMethodCall { ref args, ref method, ref receiver, .. }) => {
+                    let mut fields = mut_ref_args(args);
+                    if is_mutating_method(method) {
+                        fields.extend(model_field(receiver).cloned());
+                    }
+                    fields
+                },
                 _ => return fold_expr(self, expr),
             };
         let new_expr = fold_expr(self, expr);
-        self.fold_assign(lhs_clone, new_expr)
+        self.fold_mutation(&mutated_fields, new_expr)
+    }
+}
+
+/// Collect the attributes of the model mutated every time an expression is evaluated, like the
+/// `Adder` detects them: the branches of the conditions, the bodies of the loops and the closures
+/// are skipped.
+pub struct MutationVisitor {
+    pub fields: HashSet<Ident>,
+}
+
+impl MutationVisitor {
+    pub fn new() -> Self {
+        MutationVisitor {
+            fields: HashSet::new(),
+        }
+    }
+
+    /// Visit the part of a conditional expression that is always evaluated.
+    /// Return false if the expression is not conditional.
+    fn visit_unconditional(&mut self, expr: &Expr) -> bool {
+        match *expr {
+            Expr::Binary(ExprBinary { ref left, op: BinOp::And(_), .. }) |
+                Expr::Binary(ExprBinary { ref left, op: BinOp::Or(_), .. }) => self.visit_expr(left),
+            Expr::Closure(_) => (),
+            ForLoop(ExprForLoop { expr: ref iterable, .. }) => {
+                self.fields.extend(mut_borrowed_field(iterable));
+                self.visit_expr(iterable);
+            },
+            If(ExprIf { ref cond, .. }) => self.visit_expr(cond),
+            Match(ExprMatch { expr: ref scrutinee, .. }) => self.visit_expr(scrutinee),
+            Expr::While(ExprWhile { ref cond, .. }) => self.visit_expr(cond),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl<'ast> Visit<'ast> for MutationVisitor {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if self.visit_unconditional(expr) {
+            return;
+        }
+        match *expr {
+            Assign(ExprAssign { ref left, .. }) | AssignOp(ExprAssignOp { ref left, .. }) =>
+                self.fields.extend(model_field(left).cloned()),
+            Call(ExprCall { ref args, .. }) => self.fields.extend(mut_ref_args(args)),
+            MethodCall(ExprMethodCall { ref args, ref method, ref receiver, .. }) => {
+                self.fields.extend(mut_ref_args(args));
+                if is_mutating_method(method) {
+                    self.fields.extend(model_field(receiver).cloned());
+                }
+            },
+            _ => (),
+        }
+        visit_expr(self, expr);
     }
 }
 
+/// The branch of a condition in which a widget is created.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Branch {
+    pub condition: Ident,
+    pub typ: Ident,
+    pub variant: Ident,
+}
+
+/// The state or the enabled state of an action, which depends on the model.
+#[derive(Debug, Eq, Hash, PartialEq)]
+pub struct ActionBinding {
+    pub expr: Expr,
+    pub name: LitStr,
+    pub property: ActionProperty,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ActionProperty {
+    Enabled,
+    State,
+}
+
+/// A setting written when an attribute of the model changes.
+#[derive(Debug, Eq, Hash, PartialEq)]
+pub struct SettingBinding {
+    pub field: Ident,
+    pub key: LitStr,
+    pub store: Expr,
+}
+
 #[derive(Debug, Eq, Hash, PartialEq)]
 pub struct Message {
+    pub branch: Option<Branch>,
     pub expr: Expr,
     pub name: Ident,
     pub widget_name: Ident,
@@ -105,59 +358,267 @@ pub struct Message {
 
 #[derive(Debug, Eq, Hash, PartialEq)]
 pub struct Property {
+    pub branch: Option<Branch>,
     pub expr: Expr,
     pub is_relm_widget: bool,
     pub name: Ident,
     pub widget_name: Ident,
 }
 
-fn create_stmts(ident: &Ident, property_map: &PropertyModelMap, msg_map: &MsgModelMap) -> Vec<Stmt> {
+/// Generate the method updating the widgets bound to the attributes of the model marked as dirty.
+/// Every binding is updated once, even when several of its attributes are dirty.
+pub fn gen_flush(maps: &ModelMaps) -> TokenStream {
+    let fields = maps.fields();
+    let dirty_idents: Vec<_> = fields.iter()
+        .map(|field| Ident::new(&format!("__relm_dirty_{}", field), field.span()))
+        .collect();
+    let mut updates = vec![];
+    let bindings =
+        group_by_binding(&maps.conditions, |condition| condition_stmt(condition.span(), condition)).into_iter()
+            .chain(group_by_binding(&maps.loops, |container| loop_stmt(container.span(), container)))
+            .chain(group_by_binding(&maps.properties, |property| property_stmt(property.name.span(), property)))
+            .chain(group_by_binding(&maps.msgs, |msg| msg_stmt(msg.name.span(), msg)))
+            .chain(group_by_binding(&maps.actions, |action| action_stmt(action.name.span(), action)))
+            .chain(group_by_binding(&maps.settings, |setting| setting_stmt(setting.key.span(), setting)));
+    for (binding_fields, stmt) in bindings {
+        let dirty = binding_fields.iter()
+            .map(|field| Ident::new(&format!("__relm_dirty_{}", field), field.span()));
+        updates.push(quote! {
+            if #(#dirty)||* {
+                #stmt
+            }
+        });
+    }
+    quote! {
+        #[allow(unused_variables, clippy::all)]
+        fn __relm_flush(&mut self) {
+            #(let #dirty_idents = self.dirty.#fields.replace(false);)*
+            #(#updates)*
+        }
+    }
+}
+
+/// Get the attributes of the model every binding depends on, with the statement updating it.
+fn group_by_binding<T: Eq + Hash, F>(map: &HashMap<Ident, HashSet<T>>, create_stmt: F) -> Vec<(Vec<&Ident>, Stmt)>
+where F: Fn(&T) -> Stmt,
+{
+    let mut fields: Vec<_> = map.keys().collect();
+    fields.sort();
+    let mut bindings: Vec<(&T, Vec<&Ident>)> = vec![];
+    for field in fields {
+        for binding in &map[field] {
+            match bindings.iter_mut().find(|(other, _)| *other == binding) {
+                Some((_, binding_fields)) => binding_fields.push(field),
+                None => bindings.push((binding, vec![field])),
+            }
+        }
+    }
+    bindings.into_iter()
+        .map(|(binding, fields)| (fields, create_stmt(binding)))
+        .collect()
+}
+
+fn create_stmts(ident: &Ident, maps: &ModelMaps) -> Vec<Stmt> {
+    let span = ident.span();
     let mut stmts = vec![];
-    stmts.append(&mut create_stmts_for_props(ident, property_map));
-    stmts.append(&mut create_stmts_for_msgs(ident, msg_map));
+    if let Some(properties) = maps.properties.get(ident) {
+        stmts.extend(properties.iter().map(|property| property_stmt(span, property)));
+    }
+    if let Some(messages) = maps.msgs.get(ident) {
+        stmts.extend(messages.iter().map(|msg| msg_stmt(span, msg)));
+    }
+    if let Some(conditions) = maps.conditions.get(ident) {
+        stmts.extend(conditions.iter().map(|condition| condition_stmt(span, condition)));
+    }
+    if let Some(loops) = maps.loops.get(ident) {
+        stmts.extend(loops.iter().map(|container| loop_stmt(span, container)));
+    }
+    if let Some(actions) = maps.actions.get(ident) {
+        stmts.extend(actions.iter().map(|action| action_stmt(span, action)));
+    }
+    if let Some(settings) = maps.settings.get(ident) {
+        stmts.extend(settings.iter().map(|setting| setting_stmt(span, setting)));
+    }
     stmts
 }
 
-fn create_stmts_for_msgs(ident: &Ident, msg_map: &MsgModelMap) -> Vec<Stmt> {
-    let mut stmts = vec![];
-    if let Some(messages) = msg_map.get(ident) {
-        for msg in messages {
-            let widget_name = &msg.widget_name;
-            let mut value = quote! {};
-            value.append_all(&[&msg.expr]);
-            let variant = &msg.name;
-            let stmt = quote_spanned! { ident.span() =>
+/// Set the state or the enabled state of an action from the model.
+fn action_stmt(span: Span, action: &ActionBinding) -> Stmt {
+    let name = &action.name;
+    let expr = &action.expr;
+    let stmt =
+        match action.property {
+            ActionProperty::Enabled => quote_spanned! { span => self.actions.set_enabled(#name, #expr); },
+            ActionProperty::State => quote_spanned! { span => self.actions.set_state(#name, &(#expr)); },
+        };
+    parse_stmt(quote_spanned! { span => {
+        #stmt
+    }})
+}
+
+/// Write an attribute of the model to its setting.
+fn setting_stmt(span: Span, setting: &SettingBinding) -> Stmt {
+    let field = &setting.field;
+    let key = &setting.key;
+    let store = &setting.store;
+    parse_stmt(quote_spanned! { span => {
+        ::relm::Settings::set(&(#store), #key, &self.model.#field);
+    }})
+}
+
+/// Rebuild the widgets of a condition when the active branch changes.
+fn condition_stmt(span: Span, condition: &Ident) -> Stmt {
+    let build_fn = gen_build_condition_ident(condition);
+    let index_fn = gen_condition_index_ident(condition);
+    parse_stmt(quote_spanned! { span => {
+        if Self::#index_fn(&self.model) != self.components.#condition.index() {
+            for child in ::gtk::prelude::ContainerExt::children(&self.widgets.#condition) {
+                ::gtk::prelude::ContainerExt::remove(&self.widgets.#condition, &child);
+            }
+            self.components.#condition = Self::#build_fn(&self.relm, &self.model, self.widgets.#condition.clone());
+        }
+    }})
+}
+
+/// Create, update and remove the widgets of a loop to match the collection.
+fn loop_stmt(span: Span, container: &Ident) -> Stmt {
+    let sync_fn = gen_sync_loop_ident(container);
+    parse_stmt(quote_spanned! { span => {
+        Self::#sync_fn(&self.relm, &self.model, self.widgets.#container.clone(),
+            &mut self.components.#container);
+    }})
+}
+
+fn msg_stmt(span: Span, msg: &Message) -> Stmt {
+    let widget_name = &msg.widget_name;
+    let mut value = quote! {};
+    value.append_all(&[&msg.expr]);
+    let variant = &msg.name;
+    let stmt =
+        if let Some(Branch { ref condition, ref typ, variant: ref branch_variant }) = msg.branch {
+            quote_spanned! { span =>
+                {
+                    if let #typ::#branch_variant { ref #widget_name, .. } = self.components.#condition {
+                        #widget_name.stream().emit(#variant(#value));
+                    }
+                }
+            }
+        }
+        else {
+            quote_spanned! { span =>
                 { self.components.#widget_name.stream().emit(#variant(#value)); }
-            };
-            let expr: Expr = parse(stmt.into())
-                .expect("parse() in create_stmts");
-            if let Block(ExprBlock { ref block, .. }) = expr {
-                stmts.push(block.stmts[0].clone());
+            }
+        };
+    parse_stmt(stmt)
+}
+
+fn property_stmt(span: Span, property: &Property) -> Stmt {
+    let widget_name = &property.widget_name;
+    let prop_name = Ident::new(&format!("set_{}", property.name), property.name.span());
+    let mut tokens = quote! {};
+    tokens.append_all(&[&property.expr]);
+    let stmt =
+        if let Some(Branch { ref condition, ref typ, ref variant }) = property.branch {
+            let widget =
+                if property.is_relm_widget {
+                    quote! { #widget_name.widget() }
+                }
+                else {
+                    quote! { #widget_name }
+                };
+            quote_spanned! { span =>
+                {
+                    if let #typ::#variant { ref #widget_name, .. } = self.components.#condition {
+                        #widget.#prop_name(#tokens);
+                    }
+                }
             }
         }
+        else {
+            // The widgets created in a branch are not cached since they are recreated with
+            // the current value when the branch changes.
+            let key = format!("{}.{}", widget_name, property.name);
+            quote_spanned! { span =>
+                {
+                    {
+                        #[allow(unused_imports)] // Only one of the traits is used, depending on the type of the value.
+                        use ::relm::{CachedPropertySetter as _, UncachedPropertySetter as _};
+                        (&::relm::PropertySetter::new(|__relm_value| {
+                            self.widgets.#widget_name.#prop_name(__relm_value);
+                        })).set_if_changed(&self.cache, #key, #tokens);
+                    }
+                }
+            }
+        };
+    parse_stmt(stmt)
+}
+
+fn parse_stmt(tokens: TokenStream) -> Stmt {
+    let expr: Expr = parse(tokens.into()).expect("parse() in parse_stmt");
+    match expr {
+        Block(ExprBlock { block, .. }) => block.stmts.into_iter().next().expect("statement"),
+        _ => panic!("Expected block"),
     }
-    stmts
 }
 
-fn create_stmts_for_props(ident: &Ident, property_map: &PropertyModelMap) -> Vec<Stmt> {
-    let mut stmts = vec![];
-    if let Some(properties) = property_map.get(ident) {
-        for property in properties {
-            let widget_name = &property.widget_name;
-            let prop_name = Ident::new(&format!("set_{}", property.name), property.name.span());
-            let mut tokens = quote! {};
-            tokens.append_all(&[&property.expr]);
-            let stmt =
-                quote_spanned! { ident.span() =>
-                    { self.widgets.#widget_name.#prop_name(#tokens); }
-                };
-            let expr: Expr = parse(stmt.into()).expect("parse() in create_stmts");
-            if let Block(ExprBlock { ref block, .. }) = expr {
-                stmts.push(block.stmts[0].clone());
+/// Check if a pattern binds a value by mutable reference, like `Some(ref mut value)`.
+fn has_ref_mut(pat: &Pat) -> bool {
+    struct RefMutVisitor {
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast> for RefMutVisitor {
+        fn visit_pat_ident(&mut self, pat_ident: &'ast PatIdent) {
+            if pat_ident.by_ref.is_some() && pat_ident.mutability.is_some() {
+                self.found = true;
             }
+            visit_pat_ident(self, pat_ident);
         }
     }
-    stmts
+
+    let mut visitor = RefMutVisitor {
+        found: false,
+    };
+    visitor.visit_pat(pat);
+    visitor.found
+}
+
+/// Check if the method is known to mutate its receiver without borrowing it in its return value.
+fn is_mutating_method(method: &Ident) -> bool {
+    MUTATING_METHODS.iter().any(|name| method == name)
+}
+
+/// Get the attribute of the model a place expression (like `self.model.items[0].name`) belongs to.
+fn model_field(expr: &Expr) -> Option<&Ident> {
+    match *expr {
+        Field(ExprField { ref base, member: Named(ref ident), .. }) if is_model_path(base) => Some(ident),
+        Field(ExprField { ref base, .. }) | Index(ExprIndex { expr: ref base, .. }) |
+            MethodCall(ExprMethodCall { receiver: ref base, .. }) | Paren(ExprParen { expr: ref base, .. }) |
+            Unary(ExprUnary { op: UnOp::Deref(_), expr: ref base, .. }) => model_field(base),
+        _ => None,
+    }
+}
+
+/// Get the attribute of the model mutably borrowed by an expression like `&mut self.model.items`
+/// or `self.model.items.iter_mut()`.
+fn mut_borrowed_field(expr: &Expr) -> Option<Ident> {
+    match *expr {
+        Reference(ExprReference { mutability: Some(_), ref expr, .. }) => model_field(expr).cloned(),
+        MethodCall(ExprMethodCall { ref method, ref receiver, .. })
+            if method.to_string().ends_with("_mut") || method == "drain" => model_field(receiver).cloned(),
+        Paren(ExprParen { ref expr, .. }) => mut_borrowed_field(expr),
+        _ => None,
+    }
+}
+
+/// Get the attributes of the model passed by mutable reference to a function.
+fn mut_ref_args(args: &Punctuated<Expr, Token![,]>) -> Vec<Ident> {
+    args.iter()
+        .filter_map(|arg| match *arg {
+            Reference(ExprReference { mutability: Some(_), ref expr, .. }) => model_field(expr).cloned(),
+            _ => None,
+        })
+        .collect()
 }
 
 fn is_model_path(expr: &Expr) -> bool {
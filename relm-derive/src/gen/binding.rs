@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Code generation for the bindings of a property of a widget to an attribute of the model, like
+//! `text: bind self.model.name`, or to a property of another widget, like
+//! `sensitive: bind check.active`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Expr, Ident, parse2};
+use syn::spanned::Spanned;
+
+use super::parser::{ModelBinding, WidgetBinding};
+
+pub const SUPPORTED_PROPERTIES: &[&str] = &["active", "active_id", "text", "value"];
+
+pub fn is_supported(property: &Ident) -> bool {
+    SUPPORTED_PROPERTIES.iter().any(|supported| property == supported)
+}
+
+/// Get the value given to the setter of the property.
+pub fn setter_value(binding: &ModelBinding) -> Expr {
+    let expr = &binding.expr;
+    let tokens =
+        match binding.property.to_string().as_ref() {
+            "active_id" => quote_spanned! { expr.span() => #expr.as_deref() },
+            "text" => quote_spanned! { expr.span() => &#expr },
+            _ => quote! { #expr },
+        };
+    parse2(tokens).expect("binding setter value")
+}
+
+/// Get the value of the property from the widget, converted to the type of the model attribute.
+fn getter(property: &Ident, widget: TokenStream) -> TokenStream {
+    match property.to_string().as_ref() {
+        "active" => quote! { #widget.is_active() },
+        "active_id" => quote! { #widget.active_id().map(|active_id| active_id.to_string()) },
+        "text" => quote! { #widget.text().to_string() },
+        "value" => quote! { #widget.value() },
+        _ => unreachable!(),
+    }
+}
+
+/// Get the signal emitted when the user changes the property.
+fn signal(property: &Ident) -> Ident {
+    let signal =
+        match property.to_string().as_ref() {
+            "active" => "connect_active_notify",
+            "active_id" | "text" => "connect_changed",
+            "value" => "connect_value_changed",
+            _ => unreachable!(),
+        };
+    Ident::new(signal, property.span())
+}
+
+/// Connect the signal of the widget to the method updating the model.
+/// The signals emitted by the setters of the view are ignored and the changes made while the
+/// component is being updated are applied after the current message.
+pub fn gen_bind_handler(widget_name: &Ident, binding: &ModelBinding) -> TokenStream {
+    let signal = signal(&binding.property);
+    let method = gen_bind_method_ident(widget_name, &binding.property);
+    quote_spanned! { binding.property.span() => {
+        use ::gtk::prelude::*;
+        let relm = relm.clone();
+        let cache = __relm_cache.clone();
+        let _ = #widget_name.#signal(move |_| {
+            ::relm::update_binding(&relm, &cache, |component: &mut Self| component.#method());
+        });
+    }}
+}
+
+/// Generate the method that copies the value of the property into the model and updates the
+/// rest of the view.
+/// The value is stored in the cache so that the flush does not set it back to the widget.
+pub fn gen_bind_method(widget_name: &Ident, binding: &ModelBinding) -> TokenStream {
+    let method = gen_bind_method_ident(widget_name, &binding.property);
+    let getter = getter(&binding.property, quote! { self.widgets.#widget_name });
+    let expr = &binding.expr;
+    let field = &binding.field;
+    let key = format!("{}.{}", widget_name, binding.property);
+    let value = setter_value(binding);
+    quote_spanned! { binding.property.span() =>
+        #[allow(unused_variables, clippy::all)]
+        fn #method(&mut self) {
+            use ::gtk::prelude::*;
+            #expr = #getter;
+            self.dirty.#field.set(true);
+            self.cache.store(#key, #value);
+            self.__relm_flush();
+        }
+    }
+}
+
+fn gen_bind_method_ident(widget_name: &Ident, property: &Ident) -> Ident {
+    Ident::new(&format!("__relm_bind_{}_{}", widget_name, property), property.span())
+}
+
+/// Bind the property of the widget to the property of the source widget with a `glib::Binding`.
+/// The binding lives as long as both widgets, so it is not saved.
+pub fn gen_widget_binding(widget_name: &Ident, binding: &WidgetBinding) -> TokenStream {
+    let source = &binding.source;
+    let source_property = gen_property_name(&binding.source_property);
+    let property = gen_property_name(&binding.property);
+    let bidirectional =
+        if binding.bidirectional {
+            quote! { .bidirectional() }
+        }
+        else {
+            quote! {}
+        };
+    let transform_to = binding.transform_to.iter();
+    let transform_from = binding.transform_from.iter();
+    quote_spanned! { binding.property.span() =>
+        let _ = ::gtk::glib::ObjectExt::bind_property(&#source, #source_property, &#widget_name, #property)
+            .sync_create()
+            #bidirectional
+            #(.transform_to(#transform_to))*
+            #(.transform_from(#transform_from))*
+            .build();
+    }
+}
+
+/// Convert a property identifier to the name of the GObject property.
+fn gen_property_name(property: &Ident) -> String {
+    property.to_string().replace('_', "-")
+}
@@ -0,0 +1,253 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Detect the cycles of messages between a widget and its relm children.
+//!
+//! A message sent to a child is also received by the parent connected to this message of the
+//! child, so a message of the parent modifying an attribute of the model sent to the child in
+//! such a message is emitted again and again.
+//! Only the unconditional mutations are taken into account, so that a message can break the cycle
+//! with a condition, like `if value != self.model.value { self.model.value = value }`.
+
+use std::collections::{HashMap, HashSet};
+
+use syn::{
+    Expr,
+    ExprCall,
+    ExprMatch,
+    ExprPath,
+    FnArg,
+    Ident,
+    ImplItem,
+    ImplItemMethod,
+    Pat,
+    PatIdent,
+    PatPath,
+    PatStruct,
+    PatTupleStruct,
+    PatType,
+    Path,
+};
+use syn::parse::{Error, Result};
+use syn::visit::{Visit, visit_expr};
+
+use super::adder::MutationVisitor;
+use super::parser::{EventValue, EventValueReturn, GtkWidget, Widget};
+use super::parser::EitherWidget::{Gtk, Relm};
+use super::walker::ModelVariableVisitor;
+
+/// A message of the parent leading to another message of the parent through a child.
+struct Edge {
+    child_msg: Ident,
+    field: Ident,
+    target: String,
+    widget_type: String,
+}
+
+/// Return an error when a message of the widget emits itself through its relm children.
+pub fn check_message_cycles(widget: &Widget, update: &ImplItem) -> Result<()> {
+    let mutated_fields = update_arms(update);
+    if mutated_fields.is_empty() {
+        return Ok(());
+    }
+    let mut edges = HashMap::new();
+    collect_edges(widget, &mutated_fields, &mut edges);
+
+    let mut msgs: Vec<_> = edges.keys().collect();
+    msgs.sort();
+    let mut visited = HashSet::new();
+    for msg in msgs {
+        let mut path = vec![];
+        if let Some(cycle) = find_cycle(msg, &edges, &mut visited, &mut path) {
+            let steps: Vec<_> = cycle.iter()
+                .map(|(msg, edge)| format!("`{}` modifies `self.model.{}`, which sends `{}` to `{}`, connected to `{}`",
+                    msg, edge.field, edge.child_msg, edge.widget_type, edge.target))
+                .collect();
+            let span = cycle[0].1.child_msg.span();
+            return Err(Error::new(span, format!("message cycle: {}", steps.join("; "))));
+        }
+    }
+    Ok(())
+}
+
+/// Depth-first search of a cycle from `msg`, returning the messages and edges forming it.
+fn find_cycle<'a>(msg: &'a str, edges: &'a HashMap<String, Vec<Edge>>, visited: &mut HashSet<&'a str>,
+    path: &mut Vec<(&'a str, &'a Edge)>) -> Option<Vec<(&'a str, &'a Edge)>>
+{
+    if let Some(index) = path.iter().position(|&(path_msg, _)| path_msg == msg) {
+        return Some(path[index..].to_vec());
+    }
+    if !visited.insert(msg) {
+        return None;
+    }
+    for edge in edges.get(msg).into_iter().flatten() {
+        path.push((msg, edge));
+        if let Some(cycle) = find_cycle(&edge.target, edges, visited, path) {
+            return Some(cycle);
+        }
+        path.pop();
+    }
+    None
+}
+
+fn collect_edges(widget: &Widget, mutated_fields: &HashMap<String, HashSet<Ident>>,
+    edges: &mut HashMap<String, Vec<Edge>>)
+{
+    match widget.widget {
+        Gtk(GtkWidget { ref condition, ref for_loop, .. }) => {
+            if let Some(ref condition) = *condition {
+                for branch in condition.branches() {
+                    collect_edges(branch, mutated_fields, edges);
+                }
+            }
+            if let Some(ref for_loop) = *for_loop {
+                collect_edges(&for_loop.widget, mutated_fields, edges);
+            }
+        },
+        Relm(ref relm_widget) => {
+            let mut child_msgs: Vec<_> = relm_widget.events.keys().collect();
+            child_msgs.sort();
+            for child_msg in child_msgs {
+                let sent_expr =
+                    match relm_widget.messages.get(child_msg) {
+                        Some(expr) => expr,
+                        None => continue,
+                    };
+                let mut visitor = ModelVariableVisitor::new();
+                visitor.visit_expr(sent_expr);
+                for event in &relm_widget.events[child_msg] {
+                    let target =
+                        match event.value {
+                            EventValue::CurrentWidget(EventValueReturn::WithoutReturn(ref value)) => msg_variant(value),
+                            _ => None,
+                        };
+                    let target =
+                        match target {
+                            Some(target) => target,
+                            None => continue,
+                        };
+                    for (msg, fields) in mutated_fields {
+                        if let Some(field) = visitor.idents.iter().find(|field| fields.contains(field)) {
+                            edges.entry(msg.clone()).or_default().push(Edge {
+                                child_msg: child_msg.clone(),
+                                field: field.clone(),
+                                target: target.clone(),
+                                widget_type: path_to_string(&widget.typ),
+                            });
+                        }
+                    }
+                }
+            }
+        },
+    }
+    for child in widget.children.iter().chain(widget.nested_views.values()) {
+        collect_edges(child, mutated_fields, edges);
+    }
+}
+
+/// Get the attributes of the model mutated by each message in the match of the update() method.
+fn update_arms(update: &ImplItem) -> HashMap<String, HashSet<Ident>> {
+    let mut arms = HashMap::new();
+    if let ImplItem::Method(ImplItemMethod { ref sig, ref block, .. }) = *update {
+        let event =
+            match sig.inputs.iter().nth(1) {
+                Some(FnArg::Typed(PatType { ref pat, .. })) =>
+                    match **pat {
+                        Pat::Ident(PatIdent { ref ident, .. }) => ident.clone(),
+                        _ => return arms,
+                    },
+                _ => return arms,
+            };
+        let mut visitor = MatchVisitor {
+            event,
+            event_match: None,
+        };
+        visitor.visit_block(block);
+        if let Some(event_match) = visitor.event_match {
+            // An arm with a guard might not be executed, so it cannot start a cycle on its own.
+            for arm in event_match.arms.iter().filter(|arm| arm.guard.is_none()) {
+                let mut mutations = MutationVisitor::new();
+                mutations.visit_expr(&arm.body);
+                for variant in pat_variants(&arm.pat) {
+                    arms.entry(variant).or_insert_with(HashSet::new).extend(mutations.fields.iter().cloned());
+                }
+            }
+        }
+    }
+    arms
+}
+
+/// Find the match on the message in the update() method.
+struct MatchVisitor<'ast> {
+    event: Ident,
+    event_match: Option<&'ast ExprMatch>,
+}
+
+impl<'ast> Visit<'ast> for MatchVisitor<'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if self.event_match.is_some() {
+            return;
+        }
+        if let Expr::Match(ref event_match) = *expr {
+            if let Expr::Path(ExprPath { ref path, .. }) = *event_match.expr {
+                if path.is_ident(&self.event) {
+                    self.event_match = Some(event_match);
+                    return;
+                }
+            }
+        }
+        visit_expr(self, expr);
+    }
+}
+
+fn pat_variants(pat: &Pat) -> Vec<String> {
+    match *pat {
+        Pat::Ident(PatIdent { ref ident, subpat: None, .. }) => vec![ident.to_string()],
+        Pat::Or(ref pat_or) => pat_or.cases.iter().flat_map(pat_variants).collect(),
+        Pat::Path(PatPath { ref path, .. }) | Pat::Struct(PatStruct { ref path, .. }) |
+            Pat::TupleStruct(PatTupleStruct { ref path, .. }) => path_variant(path).into_iter().collect(),
+        _ => vec![],
+    }
+}
+
+/// Get the variant of a message, like `Msg::Changed(value)`.
+fn msg_variant(expr: &Expr) -> Option<String> {
+    match *expr {
+        Expr::Path(ExprPath { ref path, .. }) => path_variant(path),
+        Expr::Call(ExprCall { ref func, .. }) =>
+            match **func {
+                Expr::Path(ExprPath { ref path, .. }) => path_variant(path),
+                _ => None,
+            },
+        _ => None,
+    }
+}
+
+fn path_variant(path: &Path) -> Option<String> {
+    path.segments.last().map(|segment| segment.ident.to_string())
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.segments.iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
@@ -0,0 +1,134 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use syn::parse::{Error, Result};
+
+/// Environment variable to dump the code generated for every widget, or for the widgets whose
+/// names are in this comma-separated list.
+const DUMP_VAR: &str = "RELM_DUMP";
+
+pub fn is_enabled(name: &str) -> bool {
+    is_selected(env::var(DUMP_VAR).ok().as_deref(), name)
+}
+
+/// Check if the widget `name` is selected by the value of `RELM_DUMP`: an empty value or `1`
+/// selects every widget.
+fn is_selected(filter: Option<&str>, name: &str) -> bool {
+    match filter {
+        None => false,
+        Some("") | Some("1") => true,
+        Some(names) => names.split(',').any(|selected| selected.trim() == name),
+    }
+}
+
+/// Write the formatted code generated for the widget `name` in the `relm` directory of `OUT_DIR`,
+/// or of the target directory when the crate has no build script.
+pub fn dump(name: &str, tokens: &TokenStream) -> Result<()> {
+    let dir = dump_dir(env::var_os("OUT_DIR"), env::var_os("CARGO_TARGET_DIR"), env::var_os("CARGO_MANIFEST_DIR"));
+    let path = dump_path(&dir, env::var("CARGO_CRATE_NAME").ok().as_deref(), name);
+    write(&path, tokens)
+        .map_err(|error| Error::new(Span::call_site(),
+            format!("cannot dump the code of {} to {}: {}", name, path.display(), error)))
+}
+
+fn write(path: &Path, tokens: &TokenStream) -> std::io::Result<()> {
+    let code =
+        match syn::parse2::<syn::File>(tokens.clone()) {
+            Ok(file) => prettyplease::unparse(&file),
+            // Still write the code that cannot be formatted, as this can help finding the issue.
+            Err(_) => tokens.to_string(),
+        };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, code)
+}
+
+fn dump_path(dir: &Path, crate_name: Option<&str>, name: &str) -> PathBuf {
+    let file_name =
+        match crate_name {
+            Some(crate_name) => format!("{}-{}.rs", crate_name, name),
+            None => format!("{}.rs", name),
+        };
+    dir.join("relm").join(file_name)
+}
+
+fn dump_dir(out_dir: Option<OsString>, target_dir: Option<OsString>, manifest_dir: Option<OsString>) -> PathBuf {
+    if let Some(out_dir) = out_dir {
+        return out_dir.into();
+    }
+    if let Some(target_dir) = target_dir {
+        return target_dir.into();
+    }
+    manifest_dir
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join("target")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use quote::quote;
+
+    use super::{dump_dir, dump_path, is_selected, write};
+
+    #[test]
+    fn output_path() {
+        let out_dir = Some("/build/out".into());
+        let target_dir = Some("/build/target".into());
+        let manifest_dir = Some("/src/app".into());
+        assert_eq!(dump_dir(out_dir, target_dir.clone(), manifest_dir.clone()), PathBuf::from("/build/out"));
+        assert_eq!(dump_dir(None, target_dir, manifest_dir.clone()), PathBuf::from("/build/target"));
+        assert_eq!(dump_dir(None, None, manifest_dir), PathBuf::from("/src/app/target"));
+
+        let dir = PathBuf::from("/build/out");
+        assert_eq!(dump_path(&dir, Some("app"), "Win"), PathBuf::from("/build/out/relm/app-Win.rs"));
+        assert_eq!(dump_path(&dir, None, "Win"), PathBuf::from("/build/out/relm/Win.rs"));
+
+        let dir = env::temp_dir().join(format!("relm-dump-{}", std::process::id()));
+        let path = dump_path(&dir, Some("app"), "Win");
+        write(&path, &quote! { struct Win { counter: i32 } }).expect("write");
+        let code = fs::read_to_string(&path).expect("read");
+        fs::remove_dir_all(&dir).expect("remove dump directory");
+        assert_eq!(code, "struct Win {\n    counter: i32,\n}\n");
+    }
+
+    #[test]
+    fn filter() {
+        assert!(!is_selected(None, "Win"));
+        assert!(is_selected(Some(""), "Win"));
+        assert!(is_selected(Some("1"), "Win"));
+        assert!(is_selected(Some("Win"), "Win"));
+        assert!(is_selected(Some("Counter, Win"), "Win"));
+        assert!(!is_selected(Some("Counter"), "Win"));
+        assert!(!is_selected(Some("Window"), "Win"));
+    }
+}
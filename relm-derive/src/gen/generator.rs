@@ -19,12 +19,14 @@
  * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
  */
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::{
     Expr,
+    ExprField,
+    ExprPath,
     Generics,
     Ident,
     Path,
@@ -33,19 +35,30 @@ use syn::{
     parse,
 };
 use syn::fold::Fold;
+use syn::parse::{Error, Result};
+use syn::Member::Named;
 use syn::spanned::Spanned;
 
 use super::parser::{
+    Condition,
+    ConditionKind,
     Event,
+    ForLoop,
     GtkWidget,
     RelmWidget,
+    UiFileObject,
     Widget,
+    dummy_ident,
 };
 use super::parser::EventValue::{CurrentWidget, ForeignWidget, NoEventValue};
 use super::parser::EventValueReturn::{CallReturn, Return, WithoutReturn};
 use super::parser::EitherWidget::{Gtk, Relm};
+use super::accelerator::gen_accelerators;
+use super::action::gen_actions;
+use super::settings::gen_connect;
+use super::binding::{gen_bind_handler, gen_bind_method, gen_widget_binding};
 use super::transformer::Transformer;
-use super::{Driver, MODEL_IDENT};
+use super::{Driver, MODEL_IDENT, PropertyModelMap};
 
 use self::WidgetType::*;
 use self::WithParentheses::{WithParens, WithoutParens};
@@ -64,14 +77,18 @@ enum WidgetType {
 
 pub struct Gen {
     pub view: TokenStream,
+    pub bind_methods: TokenStream,
     pub relm_widgets: HashMap<Ident, Path>,
     pub relm_components: HashMap<Ident, Path>,
     pub streams_to_save: HashSet<Ident>,
     pub container_impl: TokenStream,
+    pub block_items: TokenStream,
+    pub block_methods: TokenStream,
+    pub blocks: Vec<(Ident, TokenStream)>,
 }
 
-pub fn gen(name: &Ident, widgets: &[Widget], driver: &mut Driver) -> Gen {
-    let mut generator = Generator::new(driver);
+pub fn gen(name: &Ident, widgets: &[Widget], driver: &mut Driver, model_properties: &PropertyModelMap) -> Result<Gen> {
+    let mut generator = Generator::new(name, Some(driver));
     let mut widget_tokens = quote! {};
     for (index, widget) in widgets.iter().enumerate() {
         // Only show the first item as the following could be non-widget like a gtk::Gesture.
@@ -120,11 +137,58 @@ pub fn gen(name: &Ident, widgets: &[Widget], driver: &mut Driver) -> Gen {
             quote! {}
         };
 
+    let view_file_watch = driver.view_file.as_ref().map(|path| gen_view_file_watch(path, widgets));
+    let cache_watches = gen_cache_watches(model_properties);
+
+    let root =
+        if driver.root_widget_is_relm {
+            quote! { #root_widget_name.widget() }
+        }
+        else {
+            quote! { #root_widget_name }
+        };
+    let (accelerators, accelerators_field) =
+        match driver.accelerators {
+            Some(ref accelerators) =>
+                (gen_accelerators(accelerators, root.clone()), quote! { accelerators: __relm_accelerators, }),
+            None => (quote! {}, quote! {}),
+        };
+    let (actions, actions_field) =
+        match driver.actions {
+            Some(ref actions) => (gen_actions(actions, root), quote! { actions: __relm_actions, }),
+            None => (quote! {}, quote! {}),
+        };
+
+    let settings = driver.settings.as_ref().map(gen_connect);
+
+    let block_names: Vec<_> = generator.blocks.iter().map(|(ident, _)| ident).collect();
+    let block_values = block_names.iter().map(|ident| gen_block_ident(ident));
+    let relm_field =
+        if block_names.is_empty() {
+            quote! {}
+        }
+        else {
+            quote! {
+                relm: relm.clone(),
+            }
+        };
+
+    let menus = driver.menus.iter().map(|menu| menu.gen());
+    let menu_names = driver.menus.iter().map(|menu| &menu.name);
+
     let view = quote_spanned! { name.span() =>
+        #(#menus)*
+
         #widget_tokens
 
+        let __relm_cache = ::relm::PropertyCache::new();
         #(#events)*
         #(#properties)*
+        #cache_watches
+        #view_file_watch
+        #actions
+        #accelerators
+        #settings
 
         #name {
             streams: #streams_name {
@@ -137,25 +201,58 @@ pub fn gen(name: &Ident, widgets: &[Widget], driver: &mut Driver) -> Gen {
             },
             components: #components_name {
                 #(#component_names,)*
+                #(#block_names: #block_values,)*
             },
             model: #model_ident,
+            #relm_field
+            #accelerators_field
+            #actions_field
+            #(#menu_names,)*
+            dirty: ::std::default::Default::default(),
+            cache: __relm_cache,
         }
     };
-    let container_impl = gen_container_impl(&generator, &widgets[0], driver.generic_types.as_ref().expect("generic types"));
+    if let Some(error) = generator.errors.drain(..).reduce(|mut errors, error| {
+        errors.combine(error);
+        errors
+    })
+    {
+        return Err(error);
+    }
+    let container_impl = gen_container_impl(&generator, &widgets[0], driver.generic_types.as_ref().expect("generic types"))?;
+    let block_items = &generator.block_items;
+    let block_methods = &generator.block_methods;
+    let bind_methods = &generator.bind_methods;
 
-    Gen {
+    Ok(Gen {
         view,
+        bind_methods: quote! {
+            #(#bind_methods)*
+        },
+        block_items: quote! {
+            #(#block_items)*
+        },
+        block_methods: quote! {
+            #(#block_methods)*
+        },
+        blocks: generator.blocks,
         relm_widgets: generator.relm_widgets,
         relm_components: generator.relm_components,
         streams_to_save: generator.streams_to_save,
         container_impl,
-    }
+    })
 }
 
 struct Generator<'a> {
+    bind_methods: Vec<TokenStream>,
+    block_items: Vec<TokenStream>,
+    block_methods: Vec<TokenStream>,
+    blocks: Vec<(Ident, TokenStream)>,
     container_names: HashMap<Option<String>, (Ident, Path)>,
     driver: Option<&'a mut Driver>,
+    errors: Vec<Error>,
     events: Vec<TokenStream>,
+    name: Ident,
     properties: Vec<TokenStream>,
     relm_components: HashMap<Ident, Path>,
     relm_widgets: HashMap<Ident, Path>,
@@ -164,11 +261,19 @@ struct Generator<'a> {
 }
 
 impl<'a> Generator<'a> {
-    fn new(driver: &'a mut Driver) -> Self {
+    // The driver is None when generating the widgets of a branch of a condition since they
+    // cannot be the root widget.
+    fn new(name: &Ident, driver: Option<&'a mut Driver>) -> Self {
         Generator {
+            bind_methods: vec![],
+            block_items: vec![],
+            block_methods: vec![],
+            blocks: vec![],
             container_names: HashMap::new(),
-            driver: Some(driver),
+            driver,
+            errors: vec![],
             events: vec![],
+            name: name.clone(),
             properties: vec![],
             relm_components: HashMap::new(),
             relm_widgets: HashMap::new(),
@@ -195,25 +300,35 @@ impl<'a> Generator<'a> {
         }
         else {
             let struct_name = &widget.typ;
-            let driver = self.driver.as_mut().expect("driver");
-            if driver.root_widget_expr.is_none() {
-                driver.root_widget_type = Some(quote! {
-                    #struct_name
-                });
-                driver.root_widget = Some(widget_name.clone());
-                driver.root_widget_expr = Some(quote! {
-                    #widget_name
-                });
+            if let Some(driver) = self.driver.as_mut() {
+                if driver.root_widget_expr.is_none() {
+                    driver.root_widget_type = Some(quote! {
+                        #struct_name
+                    });
+                    driver.root_widget = Some(widget_name.clone());
+                    driver.root_widget_expr = Some(quote! {
+                        #widget_name
+                    });
+                }
             }
             quote! {
             }
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn add_or_create_widget(&mut self, parent: Option<&Ident>, parent_widget_type: WidgetType, widget_name: &Ident,
-        widget_type_ident: &Path, init_parameters: &[Expr], is_container: bool) -> TokenStream
+        widget_type_ident: &Path, init_parameters: &[Expr], named_parameters: &[(Ident, Expr)], is_container: bool)
+        -> TokenStream
     {
-        let init_parameters = gen_model_param(init_parameters, WithParens);
+        let init_parameters =
+            // A widget without parameters takes the default ones, like its named parameters.
+            if named_parameters.is_empty() && !init_parameters.is_empty() {
+                gen_model_param(init_parameters, WithParens)
+            }
+            else {
+                gen_named_model_params(widget_type_ident, named_parameters)
+            };
         if let Some(parent) = parent {
             if parent_widget_type == IsGtk {
                 let add_method =
@@ -238,16 +353,17 @@ impl<'a> Generator<'a> {
             }
         }
         else {
-            let driver = self.driver.as_mut().expect("driver");
-            if driver.root_widget_expr.is_none() {
-                driver.root_widget_type = Some(quote_spanned! { widget_name.span() =>
-                    <#widget_type_ident as ::relm::Widget>::Root
-                });
-                driver.root_widget = Some(widget_name.clone());
-                driver.root_widget_expr = Some(quote! {
-                    #widget_name
-                });
-                driver.root_widget_is_relm = true;
+            if let Some(driver) = self.driver.as_mut() {
+                if driver.root_widget_expr.is_none() {
+                    driver.root_widget_type = Some(quote_spanned! { widget_name.span() =>
+                        <#widget_type_ident as ::relm::Widget>::Root
+                    });
+                    driver.root_widget = Some(widget_name.clone());
+                    driver.root_widget_expr = Some(quote! {
+                        #widget_name
+                    });
+                    driver.root_widget_is_relm = true;
+                }
             }
             if is_container {
                 quote_spanned! { widget_name.span() =>
@@ -398,7 +514,23 @@ impl<'a> Generator<'a> {
         }
 
         let construct_widget = gen_construct_widget(widget, gtk_widget);
+        let create_builder =
+            if let Some(UiFileObject { ui: Some(ref ui), .. }) = gtk_widget.ui_object {
+                quote_spanned! { widget_name.span() =>
+                    let __relm_builder = ::gtk::Builder::from_string(#ui);
+                }
+            }
+            else {
+                quote! { }
+            };
         self.collect_events(widget, gtk_widget);
+        for binding in &gtk_widget.model_bindings {
+            self.events.push(gen_bind_handler(widget_name, binding));
+            self.bind_methods.push(gen_bind_method(widget_name, binding));
+        }
+        for binding in &gtk_widget.widget_bindings {
+            self.events.push(gen_widget_binding(widget_name, binding));
+        }
 
         let children: Vec<_> = widget.children.iter()
             .map(|child| self.widget(child, Some(widget_name), IsGtk, true))
@@ -412,6 +544,16 @@ impl<'a> Generator<'a> {
             quote_spanned! { widget_name.span() => gtk::prelude::StyleContextExt::add_class(&#widget_name.style_context(), &#style_class); }
         ).collect();
 
+        // The state is restored before the widget is shown, for the default size of the windows
+        // to be used.
+        // The widgets which cannot be persisted are reported on the attribute.
+        let persist = widget.persist.as_ref().map(|name| {
+            let widget = Ident::new(&widget_name.to_string(), name.span());
+            quote_spanned! { name.span() =>
+                ::relm::persist(&#widget, #name);
+            }
+        });
+
         let show =
             if show {
                 quote_spanned! { widget_name.span() =>
@@ -422,15 +564,268 @@ impl<'a> Generator<'a> {
                 quote! { }
             };
 
+        let condition =
+            if let Some(ref condition) = gtk_widget.condition {
+                self.condition(widget_name, condition)
+            }
+            else {
+                quote! { }
+            };
+
+        let for_loop =
+            if let Some(ref for_loop) = gtk_widget.for_loop {
+                self.for_loop(widget_name, parent, for_loop)
+            }
+            else {
+                quote! { }
+            };
+
         quote_spanned! { widget_name.span() =>
+            #create_builder
             let #widget_name: #struct_name = #construct_widget;
             #(#properties)*
             #(#children)*
             #add_child_or_show_all
             #(#set_style_classes)*
+            #persist
             #show
             #(#visible_properties)*
             #(#child_properties)*
+            #condition
+            #for_loop
+        }
+    }
+
+    /// Generate the enum holding the widgets of the active branch of the condition and the
+    /// methods to find the active branch and to build its widgets.
+    /// Return the call to build the widgets in the view.
+    fn condition(&mut self, container: &Ident, condition: &Condition) -> TokenStream {
+        let typ = gen_condition_type(&self.name, condition);
+        let build_fn = gen_build_condition_ident(container);
+        let index_fn = gen_condition_index_ident(container);
+        let model_ident = Ident::new(MODEL_IDENT, Span::call_site());
+
+        let mut variants = vec![];
+        let mut variant_idents = vec![];
+        let mut builds = vec![];
+        for (index, widget) in condition.branches().into_iter().enumerate() {
+            let variant = gen_branch_variant(index);
+            let mut generator = Generator::new(&self.name, None);
+            let widget_tokens = generator.widget(widget, Some(container), IsGtk, true);
+            self.errors.append(&mut generator.errors);
+            for (container_name, _) in generator.container_names.values() {
+                self.errors.push(Error::new(container_name.span(),
+                    "cannot use the #[container] attribute inside a condition"));
+            }
+            let events = &generator.events;
+            let properties = &generator.properties;
+            let mut fields = vec![];
+            gen_branch_fields(widget, &mut fields);
+            let (names, types): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+            variants.push(quote! {
+                #variant { #(#names: #types),* }
+            });
+            builds.push(quote_spanned! { widget.name.span() => {
+                #widget_tokens
+                #(#events)*
+                #(#properties)*
+                ::gtk::prelude::WidgetExt::show(&#container);
+                #typ::#variant { #(#names),* }
+            }});
+            variant_idents.push(variant);
+        }
+        if condition.has_empty_branch() {
+            let variant = Ident::new("Empty", Span::call_site());
+            variants.push(quote! {
+                #variant
+            });
+            builds.push(quote! {{
+                ::gtk::prelude::WidgetExt::hide(&#container);
+                #typ::#variant
+            }});
+            variant_idents.push(variant);
+        }
+        let indices: Vec<_> = (0..variant_idents.len()).collect();
+
+        let (index_expr, build_expr) =
+            match condition.kind {
+                ConditionKind::If(ref branches, _) => {
+                    // The last branch is either the else branch or the empty branch.
+                    let last_index = indices.len() - 1;
+                    let mut index_expr = quote! { #last_index };
+                    let mut build_expr = builds.last().cloned().expect("else branch");
+                    for (index, (condition, _)) in branches.iter().enumerate().rev() {
+                        let mut remover = Transformer::new(MODEL_IDENT);
+                        let condition = remover.fold_expr(condition.clone());
+                        let build = &builds[index];
+                        index_expr = quote! {
+                            if #condition { #index } else { #index_expr }
+                        };
+                        build_expr = quote! {
+                            if #condition #build else { #build_expr }
+                        };
+                    }
+                    (index_expr, build_expr)
+                },
+                ConditionKind::Match(ref expr, ref arms) => {
+                    let mut remover = Transformer::new(MODEL_IDENT);
+                    let expr = remover.fold_expr((**expr).clone());
+                    let patterns: Vec<_> = arms.iter()
+                        .map(|arm| {
+                            let pat = &arm.pat;
+                            match arm.guard {
+                                Some(ref guard) => {
+                                    let mut remover = Transformer::new(MODEL_IDENT);
+                                    let guard = remover.fold_expr(guard.clone());
+                                    quote! { #pat if #guard }
+                                },
+                                None => quote! { #pat },
+                            }
+                        })
+                        .collect();
+                    (quote! {
+                        match #expr {
+                            #(#patterns => #indices,)*
+                        }
+                    }, quote! {
+                        match #expr {
+                            #(#patterns => #builds,)*
+                        }
+                    })
+                },
+            };
+
+        self.block_items.push(quote! {
+            #[allow(dead_code, clippy::all)]
+            pub enum #typ {
+                #(#variants,)*
+            }
+
+            impl #typ {
+                fn index(&self) -> usize {
+                    match *self {
+                        #(#typ::#variant_idents { .. } => #indices,)*
+                    }
+                }
+            }
+        });
+        self.block_methods.push(quote! {
+            #[allow(unused_variables, clippy::all)]
+            fn #index_fn(#model_ident: &<Self as ::relm::Update>::Model) -> usize {
+                #index_expr
+            }
+
+            #[allow(unused_variables, clippy::all)]
+            fn #build_fn(relm: &::relm::Relm<Self>, #model_ident: &<Self as ::relm::Update>::Model,
+                #container: ::gtk::Box) -> #typ
+            {
+                #build_expr
+            }
+        });
+        self.blocks.push((container.clone(), quote! { #typ }));
+
+        let branch = gen_block_ident(container);
+        quote! {
+            let #branch = Self::#build_fn(relm, &#model_ident, #container.clone());
+        }
+    }
+
+    /// Generate the struct holding the widgets created for an item of the loop and the method
+    /// creating, updating and removing these widgets to match the collection.
+    /// Return the creation of the widgets in the view.
+    fn for_loop(&mut self, container: &Ident, parent: Option<&Ident>, for_loop: &ForLoop) -> TokenStream {
+        let typ = gen_loop_type(&self.name, for_loop);
+        let sync_fn = gen_sync_loop_ident(container);
+        let model_ident = Ident::new(MODEL_IDENT, Span::call_site());
+
+        let widget = &*for_loop.widget;
+        let mut generator = Generator::new(&self.name, None);
+        let widget_tokens = generator.widget(widget, Some(container), IsGtk, true);
+        self.errors.append(&mut generator.errors);
+        for (container_name, _) in generator.container_names.values() {
+            self.errors.push(Error::new(container_name.span(), "cannot use the #[container] attribute inside a loop"));
+        }
+        let events = &generator.events;
+        let properties = &generator.properties;
+        let mut updates = vec![];
+        gen_update_calls(widget, &mut updates);
+        let mut fields = vec![];
+        gen_branch_fields(widget, &mut fields);
+        let (names, types): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+        let root = &widget.name;
+        let root_widget =
+            match widget.widget {
+                Gtk(_) => quote! { __relm_item.#root },
+                Relm(_) => quote! { __relm_item.#root.widget() },
+            };
+        let mut remover = Transformer::new(MODEL_IDENT);
+        let expr = remover.fold_expr(for_loop.expr.clone());
+        let mut remover = Transformer::new(MODEL_IDENT);
+        let key = remover.fold_expr(for_loop.key.clone());
+        let pat = &for_loop.pat;
+
+        self.block_items.push(quote! {
+            #[allow(dead_code)]
+            pub struct #typ {
+                pub __relm_key: Box<dyn ::std::any::Any>,
+                #(pub #names: #types,)*
+            }
+        });
+        // The widgets are keyed by the key of the items: the widgets of the items that are still
+        // there are updated and moved to their new position, the others are created or removed.
+        // Hence, the events of the widgets always refer to the item they were created for.
+        self.block_methods.push(quote! {
+            #[allow(unused_variables, clippy::all)]
+            fn #sync_fn(relm: &::relm::Relm<Self>, #model_ident: &<Self as ::relm::Update>::Model,
+                #container: ::gtk::Box, __relm_items: &mut Vec<#typ>)
+            {
+                let mut __relm_old_items: Vec<Option<#typ>> = __relm_items.drain(..).map(Some).collect();
+                for #pat in #expr {
+                    let __relm_key = #key;
+                    let __relm_old_item = __relm_old_items.iter_mut()
+                        .find(|item| item.as_ref()
+                            .map_or(false, |item| item.__relm_key.downcast_ref() == Some(&__relm_key)))
+                        .and_then(Option::take);
+                    let __relm_item =
+                        match __relm_old_item {
+                            Some(__relm_item) => {
+                                {
+                                    let #typ { #(ref #names,)* .. } = __relm_item;
+                                    #(#updates)*
+                                }
+                                __relm_item
+                            },
+                            None => {
+                                #widget_tokens
+                                #(#events)*
+                                #(#properties)*
+                                #typ { __relm_key: Box::new(__relm_key), #(#names),* }
+                            },
+                        };
+                    ::gtk::prelude::BoxExt::reorder_child(&#container, &#root_widget, __relm_items.len() as i32);
+                    __relm_items.push(__relm_item);
+                }
+                for __relm_item in __relm_old_items.into_iter().flatten() {
+                    ::gtk::prelude::ContainerExt::remove(&#container, &#root_widget);
+                }
+            }
+        });
+        self.blocks.push((container.clone(), quote! { Vec<#typ> }));
+
+        // The items are laid out like the other children of the parent box.
+        let parent = parent.iter();
+        let items = gen_block_ident(container);
+        quote! {
+            #(
+                let _ = ::gtk::glib::ObjectExt::bind_property(&#parent, "orientation", &#container, "orientation")
+                    .sync_create()
+                    .build();
+                let _ = ::gtk::glib::ObjectExt::bind_property(&#parent, "spacing", &#container, "spacing")
+                    .sync_create()
+                    .build();
+            )*
+            let mut #items = Vec::new();
+            Self::#sync_fn(relm, &#model_ident, #container.clone(), &mut #items);
         }
     }
 
@@ -441,6 +836,10 @@ impl<'a> Generator<'a> {
         let widget_name = &widget.name;
         let widget_type_ident = &widget.typ;
         self.set_container(widget, widget_name, widget_type_ident, true);
+        if let Some(ref name) = widget.persist {
+            self.errors.push(Error::new(name.span(),
+                "the #[persist] attribute can only be used on a gtk::Window or a gtk::Paned"));
+        }
         let relm_component_type = gen_relm_component_type(widget.is_container, widget_type_ident);
         self.relm_components.insert(widget.name.clone(), relm_component_type);
 
@@ -459,7 +858,8 @@ impl<'a> Generator<'a> {
         self.properties.append(&mut visible_properties);
 
         let add_or_create_widget = self.add_or_create_widget(
-            parent, parent_widget_type, widget_name, widget_type_ident, &widget.init_parameters, widget.is_container);
+            parent, parent_widget_type, widget_name, widget_type_ident, &widget.init_parameters,
+            &relm_widget.named_parameters, widget.is_container);
         let child_properties = gen_set_child_prop_calls(widget, parent, parent_widget_type, IsRelm);
         let messages = self.messages(widget, relm_widget);
 
@@ -496,7 +896,9 @@ impl<'a> Generator<'a> {
                     else {
                         "#[container]".to_string()
                     };
-                panic!("Cannot use the {} attribute twice in the same widget", attribute);
+                self.errors.push(Error::new(widget_name.span(),
+                    format!("cannot use the {} attribute twice in the same widget", attribute)));
+                return;
             }
             if is_relm_widget {
                 self.relm_components.insert(widget_name.clone(), widget_type.clone());
@@ -516,20 +918,157 @@ impl<'a> Generator<'a> {
     }
 }
 
+/// Get the name and type of the widgets that are saved in the branch of a condition.
+fn gen_branch_fields(widget: &Widget, fields: &mut Vec<(Ident, TokenStream)>) {
+    let typ = &widget.typ;
+    let field_type =
+        match widget.widget {
+            Gtk(_) => quote! { #typ },
+            Relm(_) => {
+                let component_type = gen_relm_component_type(widget.is_container, typ);
+                quote! { #component_type }
+            },
+        };
+    fields.push((widget.name.clone(), field_type));
+    for child in widget.children.iter().chain(widget.nested_views.values()) {
+        gen_branch_fields(child, fields);
+    }
+}
+
+/// Get the calls updating the properties of the widgets created by a loop.
+/// Literal values are only set when creating the widgets.
+fn gen_update_calls(widget: &Widget, updates: &mut Vec<TokenStream>) {
+    let name = &widget.name;
+    let ident =
+        match widget.widget {
+            Gtk(_) => quote! { #name },
+            Relm(_) => quote! { #name.widget() },
+        };
+    for (key, value) in &widget.properties {
+        if let Expr::Lit(_) = *value {
+            continue;
+        }
+        let mut remover = Transformer::new(MODEL_IDENT);
+        let value = remover.fold_expr(value.clone());
+        let property_func = Ident::new(&format!("set_{}", key), key.span());
+        updates.push(quote! {
+            #ident.#property_func(#value);
+        });
+    }
+    if let Relm(ref relm_widget) = widget.widget {
+        for (variant, value) in &relm_widget.messages {
+            if let Expr::Lit(_) = *value {
+                continue;
+            }
+            let mut remover = Transformer::new(MODEL_IDENT);
+            let value = remover.fold_expr(value.clone());
+            updates.push(quote! {
+                #name.stream().emit(#variant(#value));
+            });
+        }
+    }
+    for child in widget.children.iter().chain(widget.nested_views.values()) {
+        gen_update_calls(child, updates);
+    }
+}
+
+fn gen_block_ident(container: &Ident) -> Ident {
+    Ident::new(&format!("{}_block", container), container.span())
+}
+
+pub fn gen_branch_variant(index: usize) -> Ident {
+    Ident::new(&format!("Branch{}", index), Span::call_site())
+}
+
+pub fn gen_build_condition_ident(condition: &Ident) -> Ident {
+    Ident::new(&format!("{}_build", condition), condition.span())
+}
+
+pub fn gen_condition_index_ident(condition: &Ident) -> Ident {
+    Ident::new(&format!("{}_index", condition), condition.span())
+}
+
+pub fn gen_condition_type(name: &Ident, condition: &Condition) -> Ident {
+    Ident::new(&format!("__{}Condition{}", name, condition.index), name.span())
+}
+
+fn gen_loop_type(name: &Ident, for_loop: &ForLoop) -> Ident {
+    Ident::new(&format!("__{}Loop{}", name, for_loop.index), name.span())
+}
+
+pub fn gen_sync_loop_ident(container: &Ident) -> Ident {
+    Ident::new(&format!("{}_sync", container), container.span())
+}
+
 fn gen_construct_widget(widget: &Widget, gtk_widget: &GtkWidget) -> TokenStream {
     let struct_name = &widget.typ;
 
-    let mut parameters = vec![];
-    for (key, value) in gtk_widget.construct_properties.iter() {
-        let key = key.to_string().replace('_', "-");
+    if let Some(UiFileObject { ref id, .. }) = gtk_widget.ui_object {
+        let message = format!("object {} in the GtkBuilder file", id);
+        return quote_spanned! { struct_name.span() =>
+            ::gtk::prelude::BuilderExtManual::object::<#struct_name>(&__relm_builder, #id).expect(#message)
+        };
+    }
+
+    // Sort the properties to generate the same code on every compilation.
+    let mut construct_properties: Vec<_> = gtk_widget.construct_properties.iter().collect();
+    construct_properties.sort_by_key(|(key, _)| key.to_string());
+    let mut setters = vec![];
+    for (index, (key, value)) in construct_properties.into_iter().enumerate() {
         let mut remover = Transformer::new(MODEL_IDENT);
-        let value = remover.fold_expr(value.clone());
-        parameters.push(quote_spanned! { struct_name.span() =>
-            (#key, &#value as &dyn ::gtk::glib::value::ToValue)
+        let value = remover.fold_expr(gen_construct_value(value));
+        let name = key.to_string().replace('_', "-");
+        let value_ident = Ident::new(&format!("__relm_value{}", index), key.span());
+        // The trait is only used when the builder has no setter for this property.
+        setters.push(quote_spanned! { key.span() =>
+            let #value_ident = #value;
+            __relm_values.push((#name, {
+                #[allow(unused_imports)] // Only one of the traits is used, depending on the value.
+                use ::relm::{NoConstructValue as _, ToConstructValue as _};
+                (&&::relm::ConstructValue(&#value_ident)).construct_value()
+            }));
+            let __relm_builder = {
+                #[allow(non_camel_case_types)]
+                trait __relm_construct_property: Sized {
+                    fn #key<VALUE: ::relm::ToValue>(self, value: VALUE) -> ::relm::ConstructProperty<Self>;
+                }
+                impl<BUILDER> __relm_construct_property for BUILDER {
+                    fn #key<VALUE: ::relm::ToValue>(self, value: VALUE) -> ::relm::ConstructProperty<Self> {
+                        ::relm::ConstructProperty::new(self, #name, value)
+                    }
+                }
+                #[allow(unused_imports)] // Only one of the traits is used, depending on the setter.
+                use ::relm::{BuilderProperty as _, ObjectBuilderProperty as _, PendingProperty as _};
+                (&&::relm::ConstructStep::new(__relm_builder.#key(#value_ident))).apply(&mut __relm_pending_properties)
+            };
         });
     }
 
     if widget.init_parameters.is_empty() {
+        let construct =
+            if setters.is_empty() {
+                quote_spanned! { struct_name.span() =>
+                    ::gtk::glib::object::Object::new::<#struct_name>(&[])
+                }
+            }
+            else {
+                // The typed builder checks the names and the types of the construct properties.
+                quote_spanned! { struct_name.span() =>
+                    #[allow(unused_imports)] // Only used for the widgets without a builder.
+                    use ::relm::ConstructBuilder as _;
+                    let __relm_builder = <#struct_name>::builder();
+                    let mut __relm_values = vec![];
+                    let mut __relm_pending_properties = vec![];
+                    #(#setters)*
+                    if __relm_pending_properties.is_empty() {
+                        __relm_builder.build()
+                    }
+                    else {
+                        // Give the construct-only properties without a setter at construction.
+                        ::relm::build_with_values::<#struct_name>(__relm_values, &__relm_pending_properties)
+                    }
+                }
+            };
         quote_spanned!(struct_name.span() => {
             if !gtk::is_initialized_main_thread() {
                 if gtk::is_initialized() {
@@ -539,9 +1078,7 @@ fn gen_construct_widget(widget: &Widget, gtk_widget: &GtkWidget) -> TokenStream
                     panic!("GTK has not been initialized. Call `gtk::init` first.");
                 }
             }
-            let parameters = [#(#parameters),*];
-            // TODO: switch to builders.
-            ::gtk::glib::object::Object::new::<#struct_name>(&parameters)
+            #construct
         })
     }
     else {
@@ -552,6 +1089,19 @@ fn gen_construct_widget(widget: &Widget, gtk_widget: &GtkWidget) -> TokenStream
     }
 }
 
+/// Pass the widgets of the view by reference, like `group: self.radio1`, since they are used
+/// after being given to the builder.
+fn gen_construct_value(value: &Expr) -> Expr {
+    if let Expr::Field(ExprField { ref base, member: Named(ref ident), .. }) = *value {
+        if let Expr::Path(ExprPath { ref path, .. }) = **base {
+            if path.is_ident(&dummy_ident("self")) && ident != "model" {
+                return parse(quote_spanned! { value.span() => &#value }.into()).expect("construct value");
+            }
+        }
+    }
+    value.clone()
+}
+
 fn gen_event_metadata(event: &Event) -> TokenStream {
     if let CurrentWidget(CallReturn(_)) = event.value {
         quote! {
@@ -678,15 +1228,17 @@ fn gen_add_widget_method(container_names: &HashMap<Option<String>, (Ident, Path)
     }
 }
 
-fn gen_container_impl(generator: &Generator, widget: &Widget, generic_types: &Generics) -> TokenStream {
+fn gen_container_impl(generator: &Generator, widget: &Widget, generic_types: &Generics) -> Result<TokenStream> {
     let where_clause = gen_where_clause(generic_types);
     let widget_type = gen_widget_type(widget);
     if generator.container_names.is_empty() {
-        quote! {
-        }
+        Ok(quote! {
+        })
     }
     else if !generator.container_names.contains_key(&None) {
-        panic!("Use of #[container=\"name\"] attribute without the default #[container].");
+        let (container_name, _) = generator.container_names.values().next().expect("container name");
+        Err(Error::new(container_name.span(),
+            "cannot use the #[container=\"name\"] attribute without the default #[container]"))
     }
     else {
         let mut container_type = None;
@@ -703,7 +1255,7 @@ fn gen_container_impl(generator: &Generator, widget: &Widget, generic_types: &Ge
         let (containers, containers_type, other_containers_func) = gen_other_containers(generator, &widget_ident,
             widget_ident_span);
 
-        quote_spanned! { widget.name.span() =>
+        Ok(quote_spanned! { widget.name.span() =>
             #containers
 
             impl #generic_types ::relm::Container for #widget_type #where_clause {
@@ -718,7 +1270,7 @@ fn gen_container_impl(generator: &Generator, widget: &Widget, generic_types: &Ge
 
                 #add_widget_method
             }
-        }
+        })
     }
 }
 
@@ -796,6 +1348,80 @@ fn gen_model_param(init_parameters: &[Expr], with_parens: WithParentheses) -> To
     }
 }
 
+/// Start from the default parameters of the widget and only set the specified ones.
+fn gen_named_model_params(widget_type_ident: &Path, named_parameters: &[(Ident, Expr)]) -> TokenStream {
+    let mut setters = vec![];
+    for (name, value) in named_parameters {
+        let mut remover = Transformer::new(MODEL_IDENT);
+        let value = remover.fold_expr(value.clone());
+        setters.push(quote_spanned! { name.span() =>
+            __relm_params.#name = ::std::convert::Into::into(#value);
+        });
+    }
+    quote_spanned! { widget_type_ident.span() => {
+        let mut __relm_params: <#widget_type_ident as ::relm::Update>::ModelParam =
+            ::std::default::Default::default();
+        #(#setters)*
+        __relm_params
+    }}
+}
+
+/// Forget the cached values of the properties set from the model when they are changed by the
+/// user.
+/// The widgets created in a branch and the relm widgets have no cached properties to watch.
+fn gen_cache_watches(properties: &PropertyModelMap) -> TokenStream {
+    // Sort the properties to generate the same code on every compilation.
+    let watched: BTreeMap<_, _> = properties.values()
+        .flatten()
+        .filter(|property| property.branch.is_none() && !property.is_relm_widget)
+        .map(|property| (format!("{}.{}", property.widget_name, property.name), property))
+        .collect();
+    let watches = watched.into_iter().map(|(key, property)| {
+        let widget_name = &property.widget_name;
+        let name = property.name.to_string();
+        quote! {
+            __relm_cache.watch(&#widget_name, #key, #name);
+        }
+    });
+    quote! {
+        #(#watches)*
+    }
+}
+
+/// Watch the file of the view in debug builds, to reload the literal values of the properties when
+/// it changes. The file is only watched when the `RELM_RELOAD` environment variable is set.
+fn gen_view_file_watch(path: &str, widgets: &[Widget]) -> TokenStream {
+    let mut objects = vec![];
+    for widget in widgets {
+        collect_reloadable_widgets(widget, &mut objects);
+    }
+    quote! {
+        #[cfg(debug_assertions)]
+        ::relm::watch_view_file(#path, include_str!(#path), vec![#(#objects),*]);
+    }
+}
+
+/// Get the GTK widgets in depth-first order, the widgets of the conditions and of the loops being
+/// created later.
+fn collect_reloadable_widgets(widget: &Widget, objects: &mut Vec<TokenStream>) {
+    match widget.widget {
+        Gtk(ref gtk_widget) if gtk_widget.condition.is_some() || gtk_widget.for_loop.is_some() => {
+            objects.push(quote! { ::std::option::Option::None });
+            return;
+        },
+        Gtk(_) => {
+            let name = &widget.name;
+            objects.push(quote! {
+                ::std::option::Option::Some(::relm::Cast::upcast::<::relm::Object>(::std::clone::Clone::clone(&#name)))
+            });
+        },
+        Relm(_) => objects.push(quote! { ::std::option::Option::None }),
+    }
+    for child in &widget.children {
+        collect_reloadable_widgets(child, objects);
+    }
+}
+
 fn gen_relm_component_type(is_container: bool, name: &Path) -> Path {
     let tokens =
         if is_container {
@@ -0,0 +1,216 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Files included in the view, like `view!("src/win.relm")`.
+//!
+//! The paths are relative to the directory of the crate and the files are included with
+//! `include_str!()` in the generated code, so that cargo rebuilds the crate when they change.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use proc_macro2::{Group, TokenStream, TokenTree};
+use syn::LitStr;
+use syn::parse::{Error, Result};
+
+use super::parser::WidgetList;
+
+/// Get the path of the included file, relative to the directory of the crate.
+fn resolve_path(literal: &LitStr) -> PathBuf {
+    let path = PathBuf::from(literal.value());
+    match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) if path.is_relative() => PathBuf::from(manifest_dir).join(path),
+        _ => path,
+    }
+}
+
+/// Read the file included in the view and return its absolute path and its content.
+pub fn read_file(literal: &LitStr) -> Result<(String, String)> {
+    let path = resolve_path(literal);
+    let content = fs::read_to_string(&path)
+        .map_err(|error| Error::new(literal.span(), format!("cannot read {}: {}", literal.value(), error)))?;
+    Ok((path.to_string_lossy().into_owned(), content))
+}
+
+/// Parse the widgets of the view written in the file.
+pub fn parse_view_file(literal: &LitStr) -> Result<WidgetList> {
+    let (path, content) = read_file(literal)?;
+    let tokens = TokenStream::from_str(&content)
+        .map_err(|error| Error::new(literal.span(), format!("invalid tokens in {}: {}", literal.value(), error)))?;
+    // The tokens cannot point inside another file, so the errors in the generated code point at
+    // the path of the file.
+    let tokens = respan(tokens, literal);
+    match syn::parse2::<WidgetList>(tokens.clone()) {
+        Ok(mut widget_list) => {
+            widget_list.included_files.push(path.clone());
+            widget_list.view_file = Some(path);
+            Ok(widget_list)
+        },
+        Err(error) => {
+            let message =
+                match locate_error(&content, &tokens) {
+                    Some((line, column)) => format!("{}:{}:{}: {}", literal.value(), line, column, error),
+                    None => format!("{}: {}", literal.value(), error),
+                };
+            Err(Error::new(literal.span(), message))
+        },
+    }
+}
+
+fn respan(tokens: TokenStream, literal: &LitStr) -> TokenStream {
+    tokens.into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(group.delimiter(), respan(group.stream(), literal));
+                new_group.set_span(literal.span());
+                TokenTree::Group(new_group)
+            },
+            mut token => {
+                token.set_span(literal.span());
+                token
+            },
+        })
+        .collect()
+}
+
+/// Find the line and the column of the token where the parsing of the view fails.
+/// Since all the tokens have the same span, search the shortest prefix of the tokens for which
+/// the parsing fails before reaching the end of the input.
+fn locate_error(content: &str, tokens: &TokenStream) -> Option<(usize, usize)> {
+    let mut positions = vec![];
+    let mut offset = 0;
+    token_positions(tokens, content, &mut offset, &mut positions)?;
+
+    let fails_before_end = |count: usize| {
+        match syn::parse2::<WidgetList>(truncate(tokens, &mut { count })) {
+            Ok(_) => false,
+            Err(error) => !error.to_string().starts_with("unexpected end of input"),
+        }
+    };
+    let mut low = 1;
+    let mut high = positions.len();
+    if !fails_before_end(high) {
+        // The view ends too early.
+        return positions.last().cloned();
+    }
+    while low < high {
+        let middle = (low + high) / 2;
+        if fails_before_end(middle) {
+            high = middle;
+        }
+        else {
+            low = middle + 1;
+        }
+    }
+    positions.get(high - 1).cloned()
+}
+
+/// Keep the first `count` tokens, in depth-first order, closing the groups that are cut.
+fn truncate(tokens: &TokenStream, count: &mut usize) -> TokenStream {
+    let mut result = vec![];
+    for token in tokens.clone() {
+        if *count == 0 {
+            break;
+        }
+        *count -= 1;
+        match token {
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(group.delimiter(), truncate(&group.stream(), count));
+                new_group.set_span(group.span());
+                result.push(TokenTree::Group(new_group));
+            },
+            token => result.push(token),
+        }
+    }
+    result.into_iter().collect()
+}
+
+/// Get the line and the column of every token, in depth-first order.
+/// Return `None` when the tokens do not match the source, for instance for the doc comments.
+fn token_positions(tokens: &TokenStream, content: &str, offset: &mut usize, positions: &mut Vec<(usize, usize)>)
+    -> Option<()>
+{
+    for token in tokens.clone() {
+        skip_whitespaces_and_comments(content, offset);
+        positions.push(line_column(content, *offset));
+        match token {
+            TokenTree::Group(group) => {
+                *offset += 1;
+                token_positions(&group.stream(), content, offset, positions)?;
+                skip_whitespaces_and_comments(content, offset);
+                *offset += 1;
+            },
+            token => {
+                let text = token.to_string();
+                if !content[*offset..].starts_with(&text) {
+                    return None;
+                }
+                *offset += text.len();
+            },
+        }
+    }
+    Some(())
+}
+
+fn skip_whitespaces_and_comments(content: &str, offset: &mut usize) {
+    loop {
+        let rest = &content[*offset..];
+        let trimmed = rest.trim_start();
+        *offset += rest.len() - trimmed.len();
+        if trimmed.starts_with("//") {
+            *offset += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+        else if trimmed.starts_with("/*") {
+            let mut depth = 0;
+            let mut index = 0;
+            while index < trimmed.len() {
+                if trimmed[index..].starts_with("/*") {
+                    depth += 1;
+                    index += 2;
+                }
+                else if trimmed[index..].starts_with("*/") {
+                    depth -= 1;
+                    index += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                else {
+                    index += trimmed[index..].chars().next().map(char::len_utf8).unwrap_or(1);
+                }
+            }
+            *offset += index;
+        }
+        else {
+            break;
+        }
+    }
+}
+
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
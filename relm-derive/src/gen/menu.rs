@@ -0,0 +1,250 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Parsing and code generation for the `menu!` macro, which declares a `gio::Menu` saved in an
+//! attribute of the widget, like:
+//!
+//! ```ignore
+//! menu! {
+//!     main_menu {
+//!         "Open" => "win.open",
+//!         "Save" => Save if self.model.modified,
+//!         section {
+//!             "Dark theme" => "win.dark_theme",
+//!         },
+//!         submenu "Mode" {
+//!             "Normal" => "win.mode::normal",
+//!             "Compact" => "win.mode::compact",
+//!         },
+//!     }
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::{Expr, Ident, LitStr, Token, braced};
+use syn::parse::{Error, Parse, ParseStream, Result};
+
+use super::action::ActionList;
+
+#[derive(Debug)]
+pub struct Menu {
+    items: Vec<MenuItem>,
+    pub name: Ident,
+}
+
+#[derive(Debug)]
+enum MenuItem {
+    Item {
+        enabled: Option<Box<Expr>>,
+        label: LitStr,
+        target: Target,
+    },
+    Section {
+        items: Vec<MenuItem>,
+        label: Option<LitStr>,
+    },
+    Submenu {
+        items: Vec<MenuItem>,
+        label: LitStr,
+    },
+}
+
+#[derive(Debug)]
+enum Target {
+    /// A detailed action name, like `win.mode::compact`.
+    Action(LitStr),
+    /// A message, emitted by an action created for this item.
+    Msg(Box<Expr>),
+}
+
+impl Parse for Menu {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        let content;
+        braced!(content in input);
+        Ok(Menu {
+            items: parse_items(&content)?,
+            name,
+        })
+    }
+}
+
+fn parse_items(input: ParseStream) -> Result<Vec<MenuItem>> {
+    let mut items = vec![];
+    while !input.is_empty() {
+        items.push(input.parse()?);
+        if !input.is_empty() {
+            let _: Token![,] = input.parse()?;
+        }
+    }
+    Ok(items)
+}
+
+impl Parse for MenuItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            let label = input.parse()?;
+            let _: Token![=>] = input.parse()?;
+            let target =
+                if input.peek(LitStr) {
+                    Target::Action(input.parse()?)
+                }
+                else {
+                    Target::Msg(input.parse()?)
+                };
+            let enabled =
+                if input.peek(Token![if]) {
+                    let _: Token![if] = input.parse()?;
+                    Some(input.parse()?)
+                }
+                else {
+                    None
+                };
+            return Ok(MenuItem::Item {
+                enabled,
+                label,
+                target,
+            });
+        }
+        let ident: Ident = input.parse()?;
+        let label = if input.peek(LitStr) { Some(input.parse()?) } else { None };
+        let content;
+        braced!(content in input);
+        let items = parse_items(&content)?;
+        match ident.to_string().as_ref() {
+            "section" => Ok(MenuItem::Section {
+                items,
+                label,
+            }),
+            "submenu" => {
+                let label = label.ok_or_else(|| Error::new(ident.span(), "expected the label of the submenu"))?;
+                Ok(MenuItem::Submenu {
+                    items,
+                    label,
+                })
+            },
+            _ => Err(Error::new(ident.span(), "expected a menu item, `section` or `submenu`")),
+        }
+    }
+}
+
+impl Menu {
+    /// Whether the menu needs the action group of the widget, to create the actions of the items
+    /// emitting a message or to enable them.
+    pub fn needs_actions(&self) -> bool {
+        fn needs_actions(items: &[MenuItem]) -> bool {
+            items.iter().any(|item| match *item {
+                MenuItem::Item { ref enabled, ref target, .. } => enabled.is_some() || matches!(target, Target::Msg(_)),
+                MenuItem::Section { ref items, .. } | MenuItem::Submenu { ref items, .. } => needs_actions(items),
+            })
+        }
+        needs_actions(&self.items)
+    }
+
+    /// Create the actions of the items emitting a message and bind the enabled state of the
+    /// actions of the items.
+    /// The items emitting a message then target their new action.
+    pub fn register_actions(&mut self, actions: &mut ActionList) -> Result<()> {
+        let prefix = self.name.to_string().replace('_', "-");
+        let mut index = 0;
+        register_actions(&mut self.items, &prefix, &mut index, actions)
+    }
+
+    /// Generate the code creating the menu in the `view()` function.
+    pub fn gen(&self) -> TokenStream {
+        let name = &self.name;
+        let menu = gen_menu(&self.items, name.span());
+        quote_spanned! { name.span() =>
+            let #name = #menu;
+        }
+    }
+}
+
+fn register_actions(items: &mut [MenuItem], prefix: &str, index: &mut usize, actions: &mut ActionList) -> Result<()> {
+    for item in items {
+        match *item {
+            MenuItem::Item { ref mut enabled, ref mut target, .. } => {
+                let name =
+                    match *target {
+                        Target::Action(ref action) => {
+                            if enabled.is_none() {
+                                continue;
+                            }
+                            let detailed_name = action.value();
+                            let name = detailed_name.split("::").next().unwrap_or_default();
+                            match name.split_once('.') {
+                                Some((action_prefix, name)) if action_prefix == actions.prefix() && actions.contains(name) =>
+                                    LitStr::new(name, action.span()),
+                                _ => return Err(Error::new(action.span(),
+                                    "only the actions of this widget can be enabled from the model")),
+                            }
+                        },
+                        Target::Msg(ref msg) => {
+                            let name = LitStr::new(&format!("{}-{}", prefix, index), proc_macro2::Span::call_site());
+                            *index += 1;
+                            actions.add(name.clone(), (**msg).clone());
+                            *target = Target::Action(LitStr::new(&format!("{}.{}", actions.prefix(), name.value()),
+                                name.span()));
+                            name
+                        },
+                    };
+                if let Some(enabled) = enabled.take() {
+                    actions.bind_enabled(name, *enabled);
+                }
+            },
+            MenuItem::Section { ref mut items, .. } | MenuItem::Submenu { ref mut items, .. } =>
+                register_actions(items, prefix, index, actions)?,
+        }
+    }
+    Ok(())
+}
+
+fn gen_menu(items: &[MenuItem], span: proc_macro2::Span) -> TokenStream {
+    let appends = items.iter().map(|item| match *item {
+        MenuItem::Item { ref label, target: Target::Action(ref action), .. } => quote_spanned! { label.span() =>
+            __relm_menu.append(::std::option::Option::Some(#label), ::std::option::Option::Some(#action));
+        },
+        MenuItem::Item { target: Target::Msg(_), .. } => unreachable!("the actions of the menu are not registered"),
+        MenuItem::Section { ref items, ref label } => {
+            let section = gen_menu(items, span);
+            let label =
+                match *label {
+                    Some(ref label) => quote_spanned! { label.span() => ::std::option::Option::Some(#label) },
+                    None => quote_spanned! { span => ::std::option::Option::None },
+                };
+            quote_spanned! { span =>
+                __relm_menu.append_section(#label, &#section);
+            }
+        },
+        MenuItem::Submenu { ref items, ref label } => {
+            let submenu = gen_menu(items, span);
+            quote_spanned! { label.span() =>
+                __relm_menu.append_submenu(::std::option::Option::Some(#label), &#submenu);
+            }
+        },
+    });
+    quote_spanned! { span => {
+        let __relm_menu = ::gtk::gio::Menu::new();
+        #(#appends)*
+        __relm_menu
+    }}
+}
@@ -21,15 +21,22 @@
 
 /*
  * TODO: automatically add the model() method with a () return type when it is not found?
- * FIXME: Doing model.text.push_str() will not cause a set_text() to be added.
- * TODO: think about conditions and loops (widget-list).
  */
 
 pub(crate) mod parser;
 
+mod accelerator;
+mod action;
 mod adder;
+mod binding;
+mod cycle;
+mod dump;
 mod generator;
+mod include;
+mod menu;
+mod settings;
 mod transformer;
+mod ui;
 mod walker;
 
 use std::collections::{HashMap, HashSet};
@@ -37,6 +44,7 @@ use std::collections::{HashMap, HashSet};
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::{
+    Expr,
     Generics,
     Ident,
     ImplItem,
@@ -44,50 +52,67 @@ use syn::{
     ItemImpl,
     Macro,
     Path,
+    Pat,
+    PatIdent,
     PatType,
     PathArguments,
     ReturnType,
     Signature,
     TypePath,
+    TypeReference,
     parse,
 };
 use syn::FnArg::{self, Typed};
 use syn::fold::Fold;
 use syn::ImplItem::{Const, Method, Verbatim};
 use syn::Item::{self, Impl};
-use syn::parse::Result;
+use syn::parse::{Error, Result};
 use syn::spanned::Spanned;
 use syn::Type;
 use syn::visit::Visit;
 
-use self::adder::{Adder, Message, Property};
+use self::accelerator::AcceleratorList;
+use self::action::ActionList;
+use self::adder::{ActionBinding, Adder, Branch, Message, Property, SettingBinding, gen_flush};
 pub use self::generator::gen_where_clause;
+use self::generator::{gen_branch_variant, gen_condition_type};
+use self::menu::Menu;
 use self::parser::EitherWidget::{Gtk, Relm};
-use self::parser::{Widget, WidgetList};
+use self::parser::{Condition, ForLoop, GtkWidget, Widget, WidgetList};
+use self::settings::SettingList;
 use self::walker::ModelVariableVisitor;
 
 const MODEL_IDENT: &str = "__relm_model";
 
+type ActionModelMap = HashMap<Ident, HashSet<ActionBinding>>;
+type ConditionModelMap = HashMap<Ident, HashSet<Ident>>;
+type LoopModelMap = HashMap<Ident, HashSet<Ident>>;
 type MsgModelMap = HashMap<Ident, HashSet<Message>>;
 type PropertyModelMap = HashMap<Ident, HashSet<Property>>;
+type SettingModelMap = HashMap<Ident, HashSet<SettingBinding>>;
 
 #[derive(Debug)]
 pub struct Driver {
+    accelerators: Option<AcceleratorList>,
+    actions: Option<ActionList>,
     data_method: Option<ImplItem>,
     generic_types: Option<Generics>,
+    menus: Vec<Menu>,
     model_type: Option<ImplItem>,
     model_param_type: Option<ImplItem>,
-    msg_model_map: Option<MsgModelMap>,
+    model_maps: Option<ModelMaps>,
     msg_type: Option<ImplItem>,
     other_methods: Vec<ImplItem>,
-    properties_model_map: Option<PropertyModelMap>,
+    post_init_method: Option<ImplItem>,
     root_method: Option<ImplItem>,
     root_type: Option<ImplItem>,
     root_widget: Option<Ident>,
     root_widget_expr: Option<TokenStream>,
     root_widget_is_relm: bool,
     root_widget_type: Option<TokenStream>,
+    settings: Option<SettingList>,
     update_method: Option<ImplItem>,
+    view_file: Option<String>,
     view_macro: Option<Macro>,
     widget_model_type: Option<Type>,
     widget_msg_type: Option<Type>,
@@ -95,11 +120,47 @@ pub struct Driver {
     widgets: HashMap<Ident, TokenStream>, // Map widget ident to widget type.
 }
 
+#[derive(Debug)]
+struct ModelMaps {
+    actions: ActionModelMap,
+    conditions: ConditionModelMap,
+    loops: LoopModelMap,
+    msgs: MsgModelMap,
+    properties: PropertyModelMap,
+    settings: SettingModelMap,
+}
+
+impl ModelMaps {
+    /// Get the attributes of the model that are bound to the view, sorted by name.
+    fn fields(&self) -> Vec<&Ident> {
+        let mut fields: Vec<_> = self.actions.keys()
+            .chain(self.conditions.keys())
+            .chain(self.loops.keys())
+            .chain(self.msgs.keys())
+            .chain(self.properties.keys())
+            .chain(self.settings.keys())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        fields.sort();
+        fields
+    }
+
+    fn is_bound(&self, field: &Ident) -> bool {
+        self.actions.contains_key(field) || self.conditions.contains_key(field) || self.loops.contains_key(field) || self.msgs.contains_key(field) ||
+            self.properties.contains_key(field) || self.settings.contains_key(field)
+    }
+}
+
 struct View {
+    bind_methods: TokenStream,
+    block_items: TokenStream,
+    block_methods: TokenStream,
+    blocks: Vec<(Ident, TokenStream)>,
     container_impl: TokenStream,
+    included_files: Vec<String>,
     item: ImplItem,
-    msg_model_map: MsgModelMap,
-    properties_model_map: PropertyModelMap,
+    model_maps: ModelMaps,
     relm_components: HashMap<Ident, Path>,
     relm_widgets: HashMap<Ident, Path>,
     streams_to_save: HashSet<Ident>,
@@ -109,21 +170,26 @@ struct View {
 impl Driver {
     fn new() -> Self {
         Driver {
+            accelerators: None,
+            actions: None,
             data_method: None,
             generic_types: None,
+            menus: vec![],
             model_type: None,
             model_param_type: None,
-            msg_model_map: None,
+            model_maps: None,
             msg_type: None,
             other_methods: vec![],
-            properties_model_map: None,
+            post_init_method: None,
             root_method: None,
             root_type: None,
             root_widget: None,
             root_widget_expr: None,
             root_widget_is_relm: false,
             root_widget_type: None,
+            settings: None,
             update_method: None,
+            view_file: None,
             view_macro: None,
             widget_model_type: None,
             widget_msg_type: None,
@@ -133,25 +199,55 @@ impl Driver {
     }
 
     fn add_set_property_to_method(&self, func: &mut ImplItem) {
-        if let Method(ImplItemMethod { ref mut block, .. }) = *func {
-            let msg_map = self.msg_model_map.as_ref().expect("update method");
-            let property_map = self.properties_model_map.as_ref().expect("update method");
-            let mut adder = Adder::new(property_map, msg_map);
+        if let Method(ImplItemMethod { ref mut block, ref sig, .. }) = *func {
+            let maps = self.model_maps.as_ref().expect("update method");
+            // The widgets are only updated once at the end of the methods that can call the
+            // flush method after their body.
+            let batch = is_mut_self_method(sig) && sig.output == ReturnType::Default;
+            let mut adder = Adder::new(maps, batch);
             *block = adder.fold_block(block.clone());
+            if adder.marked_dirty {
+                let body = &block;
+                *block = parse(quote! {{
+                    #[allow(clippy::let_unit_value, clippy::redundant_closure_call)]
+                    let () = (|| #body)();
+                    self.__relm_flush();
+                }}.into()).expect("method block");
+            }
         }
     }
 
-    fn collect_bindings(&mut self, widget: &Widget, msg_model_map: &mut MsgModelMap, properties_model_map: &mut PropertyModelMap) {
-        get_properties_model_map(widget, properties_model_map);
-        get_msg_model_map(widget, msg_model_map);
-        self.add_widgets(widget, properties_model_map);
+    fn collect_bindings(&mut self, name: &Ident, widget: &Widget, branch: Option<&Branch>, maps: &mut ModelMaps) {
+        get_properties_model_map(widget, branch, &mut maps.properties);
+        get_msg_model_map(widget, branch, &mut maps.msgs);
+        if branch.is_none() {
+            // The widgets created in a branch are saved in the condition, not in the struct.
+            self.add_widgets(widget, &maps.properties);
+        }
 
         for nested_view in widget.nested_views.values() {
-            self.collect_bindings(nested_view, msg_model_map, properties_model_map);
+            self.collect_bindings(name, nested_view, branch, maps);
         }
 
         for child in &widget.children {
-            self.collect_bindings(child, msg_model_map, properties_model_map);
+            self.collect_bindings(name, child, branch, maps);
+        }
+
+        if let Gtk(GtkWidget { condition: Some(ref condition), .. }) = widget.widget {
+            get_condition_model_map(&widget.name, condition, &mut maps.conditions);
+            for (index, branch_widget) in condition.branches().into_iter().enumerate() {
+                let branch = Branch {
+                    condition: widget.name.clone(),
+                    typ: gen_condition_type(name, condition),
+                    variant: gen_branch_variant(index),
+                };
+                self.collect_bindings(name, branch_widget, Some(&branch), maps);
+            }
+        }
+
+        // The widgets created in a loop are all updated when the collection changes.
+        if let Gtk(GtkWidget { for_loop: Some(ref for_loop), .. }) = widget.widget {
+            get_loop_model_map(&widget.name, for_loop, &mut maps.loops);
         }
     }
 
@@ -174,7 +270,8 @@ impl Driver {
         }
     }
 
-    fn create_struct(&self, typ: &Type, relm_widgets: &HashMap<Ident, Path>, relm_components: &HashMap<Ident, Path>, streams_to_save: &HashSet<Ident>, generics: &Generics) -> TokenStream {
+    #[allow(clippy::too_many_arguments)]
+    fn create_struct(&self, typ: &Type, relm_widgets: &HashMap<Ident, Path>, relm_components: &HashMap<Ident, Path>, streams_to_save: &HashSet<Ident>, blocks: &[(Ident, TokenStream)], generics: &Generics) -> TokenStream {
         let where_clause = gen_where_clause(generics);
         let root_widget_name = self.root_widget.as_ref().expect("root widget name");
         let widgets = self.widgets.iter()
@@ -189,9 +286,11 @@ impl Driver {
             let components = relm_components.iter()
                 .map(|(ident, tokens)| (ident.clone(), tokens));
             let (idents, types): (Vec<Ident>, Vec<_>) = components.unzip();
+            let (block_idents, block_types): (Vec<_>, Vec<_>) = blocks.iter().cloned().unzip();
             quote! {
                 pub struct #components_name {
                     #(pub #idents: #types,)*
+                    #(pub #block_idents: #block_types,)*
                 }
             }
         };
@@ -240,6 +339,38 @@ impl Driver {
                 }
             }
         };
+        // The relm handle is needed to connect the events of the widgets created in the update()
+        // method.
+        let relm_field =
+            if blocks.is_empty() {
+                quote! {}
+            }
+            else {
+                quote! {
+                    relm: ::relm::Relm<#typ>,
+                }
+            };
+        let accelerators_field =
+            if self.accelerators.is_some() {
+                quote! {
+                    accelerators: ::relm::Accelerators<<#typ as ::relm::Update>::Msg>,
+                }
+            }
+            else {
+                quote! {}
+            };
+        let actions_field =
+            if self.actions.is_some() {
+                quote! {
+                    actions: ::relm::Actions<<#typ as ::relm::Update>::Msg>,
+                }
+            }
+            else {
+                quote! {}
+            };
+        let menu_names = self.menus.iter().map(|menu| &menu.name);
+        let dirty_name = Ident::new(&format!("__{}Dirty", get_name(typ)), Span::call_site());
+        let dirty_fields = self.model_maps.as_ref().expect("model maps").fields();
         quote_spanned! { typ.span() =>
             #[allow(dead_code, missing_docs)]
             pub struct #typ #where_clause {
@@ -247,29 +378,63 @@ impl Driver {
                 components: #components_name,
                 widgets: #widgets_name,
                 model: #widget_model_type,
+                #relm_field
+                #accelerators_field
+                #actions_field
+                #(#menu_names: ::gtk::gio::Menu,)*
+                dirty: #dirty_name,
+                cache: ::relm::PropertyCache,
             }
 
             #components
 
+            #[derive(Default)]
+            pub struct #dirty_name {
+                #(pub #dirty_fields: ::std::cell::Cell<bool>,)*
+            }
+
             #streams
 
             #widgets
         }
     }
 
-    fn gen_widget(&mut self, input: TokenStream) -> TokenStream {
-        let mut ast: Item = parse(input.into()).expect("parse_item() in gen_widget()");
+    fn gen_widget(&mut self, input: TokenStream, dump: bool) -> Result<TokenStream> {
+        let mut ast: Item = parse(input.into())?;
         if let Impl(ItemImpl { attrs, defaultness, unsafety, impl_token, generics, trait_, self_ty, items, brace_token }
                     ) = ast
         {
             self.generic_types = Some(generics.clone());
+            if !matches!(*self_ty, Type::Path(_)) {
+                return Err(Error::new(self_ty.span(), "expected the name of the widget"));
+            }
             let name = get_name(&self_ty);
             let mut new_items = vec![];
             let mut update_items = vec![];
+            let mut model_params = quote! {};
             for item in items {
                 let mut i = item.clone();
                 match item {
-                    Const(..) => panic!("Unexpected const item"),
+                    Const(ref item) => return Err(Error::new(item.span(), "unexpected const item in a widget")),
+                    ImplItem::Macro(mac) if mac.mac.path.is_ident("accelerators") => {
+                        if self.accelerators.is_some() {
+                            return Err(Error::new(mac.span(), "duplicate accelerators! macro"));
+                        }
+                        self.accelerators = Some(mac.mac.parse_body()?);
+                    },
+                    ImplItem::Macro(mac) if mac.mac.path.is_ident("menu") => self.menus.push(mac.mac.parse_body()?),
+                    ImplItem::Macro(mac) if mac.mac.path.is_ident("actions") => {
+                        if self.actions.is_some() {
+                            return Err(Error::new(mac.span(), "duplicate actions! macro"));
+                        }
+                        self.actions = Some(mac.mac.parse_body()?);
+                    },
+                    ImplItem::Macro(mac) if mac.mac.path.is_ident("settings") => {
+                        if self.settings.is_some() {
+                            return Err(Error::new(mac.span(), "duplicate settings! macro"));
+                        }
+                        self.settings = Some(mac.mac.parse_body()?);
+                    },
                     ImplItem::Macro(mac) => self.view_macro = Some(mac.mac),
                     Method(ImplItemMethod { sig, .. }) => {
                         match sig.ident.to_string().as_ref() {
@@ -277,13 +442,17 @@ impl Driver {
                             "root" => self.root_method = Some(i),
                             "model" => {
                                 self.widget_model_type = Some(get_return_type(sig));
-                                add_model_param(&mut i, &mut self.model_param_type);
+                                model_params = add_model_param(&mut i, &mut self.model_param_type, &name, &generics)?;
                                 update_items.push(i);
                             },
                             "subscriptions" => update_items.push(i),
                             "init_view" | "on_add" => new_items.push(i),
+                            // Only the associated functions with the signature of the Widget trait are moved.
+                            "command_line" if is_message_function(&sig, 1) => new_items.push(i),
+                            "open" if is_message_function(&sig, 2) => new_items.push(i),
+                            "post_init" => self.post_init_method = Some(i),
                             "update" => {
-                                self.widget_msg_type = Some(get_second_param_type(&sig));
+                                self.widget_msg_type = Some(get_second_param_type(&sig)?);
                                 self.update_method = Some(i)
                             },
                             _ => self.other_methods.push(i),
@@ -295,51 +464,83 @@ impl Driver {
                             "Model" => self.model_type = Some(i),
                             "ModelParam" => self.model_param_type = Some(i),
                             "Msg" => self.msg_type = Some(i),
-                            _ => panic!("Unexpected type item {:?}", typ.ident),
+                            _ => return Err(Error::new(typ.ident.span(),
+                                format!("unexpected associated type `{}` in a widget", typ.ident))),
                         }
                     },
-                    Verbatim(..) => panic!("Unexpected verbatim item"),
-                    _ => panic!("Unexpected item"),
+                    Verbatim(ref tokens) => return Err(Error::new(tokens.span(), "unexpected item in a widget")),
+                    ref item => return Err(Error::new(item.span(), "unexpected item in a widget")),
                 }
             }
-            let view =
-                match self.get_view(&name, &self_ty) {
-                    Ok(view) => view,
-                    Err(error) => return error.to_compile_error(),
-                };
+            if self.view_macro.is_none() {
+                return Err(Error::new(self_ty.span(), "missing the view! macro"));
+            }
+            for menu in &mut self.menus {
+                if menu.needs_actions() {
+                    menu.register_actions(self.actions.get_or_insert_with(ActionList::default))?;
+                }
+            }
+            if self.widget_model_type.is_none() {
+                return Err(Error::new(self_ty.span(), "missing the model() method"));
+            }
+            if let Some(ref settings) = self.settings {
+                for item in &mut update_items {
+                    add_settings_loading(item, settings);
+                }
+            }
+            if self.update_method.is_none() {
+                return Err(Error::new(self_ty.span(), "missing the update() method"));
+            }
+            let view = self.get_view(&name, &self_ty)?;
+            cycle::check_message_cycles(&view.widget, self.update_method.as_ref().expect("update method"))?;
             if let Some(on_add) = gen_set_child_prop_calls(&view.widget) {
                 new_items.push(on_add);
             }
-            self.msg_model_map = Some(view.msg_model_map);
-            self.properties_model_map = Some(view.properties_model_map);
+            self.model_maps = Some(view.model_maps);
             new_items.push(view.item);
+            if let Some(mut post_init) = self.post_init_method.take() {
+                // The model can be modified from the widgets, so the view needs to be updated.
+                self.add_set_property_to_method(&mut post_init);
+                new_items.push(post_init);
+            }
             self.widgets.insert(self.root_widget.clone().expect("root widget"),
             self.root_widget_type.clone().expect("root widget type"));
-            let widget_struct = self.create_struct(&self_ty, &view.relm_widgets, &view.relm_components, &view.streams_to_save, &generics);
+            let widget_struct = self.create_struct(&self_ty, &view.relm_widgets, &view.relm_components, &view.streams_to_save, &view.blocks, &generics);
             new_items.push(self.get_root_type());
             if let Some(data_method) = self.get_data_method() {
                 new_items.push(data_method);
             }
             new_items.push(self.get_root());
-            let other_methods = self.get_other_methods(&self_ty, &generics);
+            let other_methods = self.get_other_methods(&self_ty, &generics, view.block_methods, view.bind_methods);
             let update_impl = self.update_impl(&self_ty, &generics, update_items);
             let widget_test_impl = self.widget_test_impl(&self_ty, &generics);
             let item = Impl(ItemImpl { attrs, defaultness, unsafety, generics, impl_token, trait_, self_ty, brace_token,
                 items: new_items });
             ast = item;
             let container_impl = view.container_impl;
-            quote! {
+            let block_items = view.block_items;
+            // Make cargo rebuild the crate when an included file changes.
+            let included_files = view.included_files;
+            let tokens = quote! {
                 #widget_struct
+                #model_params
                 #ast
                 #container_impl
+                #block_items
                 #update_impl
                 #widget_test_impl
 
                 #other_methods
+
+                #(const _: &str = include_str!(#included_files);)*
+            };
+            if dump || dump::is_enabled(&name.to_string()) {
+                dump::dump(&name.to_string(), &tokens)?;
             }
+            Ok(tokens)
         }
         else {
-            panic!("Expected impl");
+            Err(Error::new(ast.span(), "#[widget] can only be used on an impl block"))
         }
     }
 
@@ -379,15 +580,21 @@ impl Driver {
         })
     }
 
-    fn get_other_methods(&mut self, typ: &Type, generics: &Generics) -> TokenStream {
+    fn get_other_methods(&mut self, typ: &Type, generics: &Generics, block_methods: TokenStream,
+        bind_methods: TokenStream) -> TokenStream
+    {
         let mut other_methods: Vec<_> = self.other_methods.drain(..).collect();
         let where_clause = gen_where_clause(generics);
         for method in &mut other_methods {
             self.add_set_property_to_method(method);
         }
+        let flush = gen_flush(self.model_maps.as_ref().expect("model maps"));
         quote! {
             impl #generics #typ #where_clause {
                 #(#other_methods)*
+                #block_methods
+                #bind_methods
+                #flush
             }
         }
     }
@@ -424,24 +631,38 @@ impl Driver {
     }
 
     fn get_view(&mut self, name: &Ident, typ: &Type) -> Result<View> {
-        let WidgetList { mut widgets } = self
+        let WidgetList { mut widgets, included_files, view_file } = self
             .view_macro
             .take()
             .expect("view_macro in impl_view()")
             .parse_body()?;
 
         self.widget_parent_id = widgets[0].parent_id.clone();
+        self.view_file = view_file;
 
-        let mut msg_model_map = HashMap::new();
-        let mut properties_model_map = HashMap::new();
+        let mut maps = ModelMaps {
+            actions: HashMap::new(),
+            conditions: HashMap::new(),
+            loops: HashMap::new(),
+            msgs: HashMap::new(),
+            properties: HashMap::new(),
+            settings: HashMap::new(),
+        };
         if let Gtk(ref mut widget) = widgets[0].widget {
             widget.relm_name = Some(typ.clone());
         }
         for widget in &widgets {
-            self.collect_bindings(widget, &mut msg_model_map, &mut properties_model_map);
+            self.collect_bindings(name, widget, None, &mut maps);
+        }
+        if let Some(ref actions) = self.actions {
+            action::get_actions_model_map(actions, &mut maps.actions);
+        }
+        if let Some(ref settings) = self.settings {
+            settings::get_settings_model_map(settings, &mut maps.settings);
         }
 
-        let generator::Gen { view, relm_widgets, relm_components, streams_to_save, container_impl } = generator::gen(name, &widgets, self);
+        let generator::Gen { view, relm_widgets, relm_components, streams_to_save, container_impl, blocks,
+            block_items, block_methods, bind_methods } = generator::gen(name, &widgets, self, &maps.properties)?;
         let model_ident = Ident::new(MODEL_IDENT, Span::call_site()); // TODO: maybe need to set Span here.
         let code = quote_spanned! { name.span() =>
             #[allow(unused_variables,clippy::all)] // Necessary to avoid warnings in case the parameters are unused.
@@ -452,10 +673,14 @@ impl Driver {
         let item = block_to_impl_item(code);
         let widget = widgets.drain(..).next().expect("first widget");
         Ok(View {
+            bind_methods,
+            block_items,
+            block_methods,
+            blocks,
             container_impl,
+            included_files,
             item,
-            msg_model_map,
-            properties_model_map,
+            model_maps: maps,
             relm_components,
             relm_widgets,
             streams_to_save,
@@ -477,6 +702,10 @@ impl Driver {
                 #model_param
                 #update
                 #(#items)*
+
+                fn into_model(self) -> ::std::option::Option<Self::Model> {
+                    ::std::option::Option::Some(self.model)
+                }
             }
         }
     }
@@ -503,14 +732,83 @@ impl Driver {
     }
 }
 
-pub fn gen_widget(input: TokenStream) -> TokenStream {
+pub fn gen_widget(input: TokenStream, dump: bool) -> TokenStream {
     let mut driver = Driver::new();
-    driver.gen_widget(input)
+    driver.gen_widget(input, dump)
+        .unwrap_or_else(|error| error.to_compile_error())
 }
 
-fn add_model_param(model_fn: &mut ImplItem, model_param_type: &mut Option<ImplItem>) {
+/// Add the relm parameter to the model function if it is missing and set the `ModelParam` type.
+/// When the function has several parameters or parameters with a `#[default]` attribute, they
+/// are named parameters: they are gathered in a struct that implements `Default`, so that a
+/// parent widget can only specify some of them by name.
+/// Return the struct of the named parameters.
+fn add_model_param(model_fn: &mut ImplItem, model_param_type: &mut Option<ImplItem>, name: &Ident,
+    generics: &Generics) -> Result<TokenStream>
+{
     let span = model_fn.span();
+    let mut params_struct = quote! {};
     if let Method(ImplItemMethod { ref mut sig, .. }) = *model_fn {
+        let has_relm_param = sig.inputs.first().map(is_relm_param).unwrap_or(false);
+        let param_count = sig.inputs.len() - has_relm_param as usize;
+        let has_default_attribute = sig.inputs.iter().any(|input| match *input {
+            Typed(PatType { ref attrs, .. }) => attrs.iter().any(|attr| attr.path.is_ident("default")),
+            _ => false,
+        });
+        if param_count > 1 || has_default_attribute {
+            if !generics.params.is_empty() {
+                return Err(Error::new(sig.ident.span(), "named parameters are not supported for generic widgets"));
+            }
+            let params_name = Ident::new(&format!("{}Params", name), name.span());
+            let relm_param = if has_relm_param { sig.inputs.first().cloned() } else { None };
+            let mut fields = vec![];
+            let mut patterns = vec![];
+            let mut types = vec![];
+            let mut defaults = vec![];
+            for input in sig.inputs.iter().skip(has_relm_param as usize) {
+                if let Typed(PatType { ref attrs, ref pat, ref ty, .. }) = *input {
+                    let field =
+                        match **pat {
+                            Pat::Ident(PatIdent { ref ident, .. }) => ident.clone(),
+                            _ => return Err(Error::new(pat.span(), "expected a named parameter")),
+                        };
+                    let default =
+                        match attrs.iter().find(|attr| attr.path.is_ident("default")) {
+                            Some(attr) if !attr.tokens.is_empty() => {
+                                let value: Expr = attr.parse_args()?;
+                                quote_spanned! { value.span() => #value }
+                            },
+                            _ => quote_spanned! { ty.span() => ::std::default::Default::default() },
+                        };
+                    fields.push(field);
+                    patterns.push(pat.clone());
+                    types.push(ty.clone());
+                    defaults.push(default);
+                }
+            }
+            let relm_param = relm_param.unwrap_or_else(|| parse(quote_spanned! { span =>
+                _: &::relm::Relm<Self>
+            }.into()).expect("relm param"));
+            let params_param: FnArg = parse(quote_spanned! { span =>
+                #params_name { #(#patterns),* }: #params_name
+            }.into()).expect("params param");
+            sig.inputs = vec![relm_param, params_param].into_iter().collect();
+            params_struct = quote_spanned! { span =>
+                #[allow(dead_code, missing_docs)]
+                pub struct #params_name {
+                    #(pub #fields: #types,)*
+                }
+
+                impl ::std::default::Default for #params_name {
+                    fn default() -> Self {
+                        #params_name {
+                            #(#fields: #defaults,)*
+                        }
+                    }
+                }
+            };
+        }
+
         let len = sig.inputs.len();
         if len == 0 || len == 1 {
             let type_tokens = quote_spanned! { span =>
@@ -530,6 +828,56 @@ fn add_model_param(model_fn: &mut ImplItem, model_param_type: &mut Option<ImplIt
             }));
         }
     }
+    Ok(params_struct)
+}
+
+/// Override the attributes of the model created by the `model()` method with the values of their
+/// settings.
+fn add_settings_loading(model_fn: &mut ImplItem, settings: &SettingList) {
+    if let Method(ImplItemMethod { ref mut block, ref sig, .. }) = *model_fn {
+        if sig.ident != "model" {
+            return;
+        }
+        let load = settings::gen_load(settings);
+        let model_ident = Ident::new(MODEL_IDENT, Span::call_site());
+        let body = &block;
+        *block = parse(quote! {{
+            #[allow(clippy::redundant_closure_call, unused_mut)]
+            let mut #model_ident = (|| #body)();
+            #load
+            #model_ident
+        }}.into()).expect("model block");
+    }
+}
+
+/// Check if the parameter is the relm parameter, like `relm: &Relm<Self>`.
+fn is_relm_param(input: &FnArg) -> bool {
+    if let Typed(PatType { ref ty, .. }) = *input {
+        if let Type::Reference(TypeReference { ref elem, .. }) = **ty {
+            if let Type::Path(TypePath { ref path, .. }) = **elem {
+                return path.segments.last().map(|segment| segment.ident == "Relm").unwrap_or(false);
+            }
+        }
+    }
+    false
+}
+
+/// Check if the method is an associated function with `param_count` parameters returning an
+/// `Option`, like `fn open(files: Vec<gio::File>, hint: String) -> Option<Msg>`.
+fn is_message_function(sig: &Signature, param_count: usize) -> bool {
+    let is_option =
+        if let ReturnType::Type(_, ref ty) = sig.output {
+            if let Type::Path(TypePath { ref path, .. }) = **ty {
+                path.segments.last().map(|segment| segment.ident == "Option").unwrap_or(false)
+            }
+            else {
+                false
+            }
+        }
+        else {
+            false
+        };
+    is_option && sig.inputs.len() == param_count && sig.inputs.iter().all(|input| matches!(input, Typed(_)))
 }
 
 fn block_to_impl_item(tokens: TokenStream) -> ImplItem {
@@ -558,7 +906,44 @@ fn get_name(typ: &Type) -> Ident {
     }
 }
 
-fn get_msg_model_map(widget: &Widget, map: &mut MsgModelMap) {
+fn get_condition_model_map(name: &Ident, condition: &Condition, map: &mut ConditionModelMap) {
+    for expr in condition.exprs() {
+        let mut visitor = ModelVariableVisitor::new();
+        visitor.visit_expr(expr);
+        for var in visitor.idents {
+            let set = map.entry(var).or_default();
+            set.insert(name.clone());
+        }
+    }
+}
+
+fn get_loop_model_map(name: &Ident, for_loop: &ForLoop, map: &mut LoopModelMap) {
+    let mut visitor = ModelVariableVisitor::new();
+    visitor.visit_expr(&for_loop.expr);
+    visitor.visit_expr(&for_loop.key);
+    visit_widget_exprs(&for_loop.widget, &mut visitor);
+    for var in visitor.idents {
+        let set = map.entry(var).or_default();
+        set.insert(name.clone());
+    }
+}
+
+/// Visit the properties and the messages of the widget and its children.
+fn visit_widget_exprs(widget: &Widget, visitor: &mut ModelVariableVisitor) {
+    for expr in widget.properties.values() {
+        visitor.visit_expr(expr);
+    }
+    if let Relm(ref relm_widget) = widget.widget {
+        for expr in relm_widget.messages.values() {
+            visitor.visit_expr(expr);
+        }
+    }
+    for child in widget.children.iter().chain(widget.nested_views.values()) {
+        visit_widget_exprs(child, visitor);
+    }
+}
+
+fn get_msg_model_map(widget: &Widget, branch: Option<&Branch>, map: &mut MsgModelMap) {
     match widget.widget {
         Gtk(_) => (),
         Relm(ref relm_widget) => {
@@ -569,6 +954,7 @@ fn get_msg_model_map(widget: &Widget, map: &mut MsgModelMap) {
                 for var in model_variables {
                     let set = map.entry(var).or_default();
                     set.insert(Message {
+                        branch: branch.cloned(),
                         expr: expr.clone(),
                         name: name.clone(),
                         widget_name: widget.name.clone(),
@@ -582,14 +968,14 @@ fn get_msg_model_map(widget: &Widget, map: &mut MsgModelMap) {
 /*
  * The map maps model variable name to a vector of tuples (widget name, property name).
  */
-fn get_properties_model_map(widget: &Widget, map: &mut PropertyModelMap) {
+fn get_properties_model_map(widget: &Widget, branch: Option<&Branch>, map: &mut PropertyModelMap) {
     match widget.widget {
-        Gtk(_) => get_map(widget, map, false),
-        Relm(_) => get_map(widget, map, true),
+        Gtk(_) => get_map(widget, branch, map, false),
+        Relm(_) => get_map(widget, branch, map, true),
     }
 }
 
-fn get_map(widget: &Widget, map: &mut PropertyModelMap, is_relm: bool) {
+fn get_map(widget: &Widget, branch: Option<&Branch>, map: &mut PropertyModelMap, is_relm: bool) {
     for (name, expr) in &widget.properties {
         let mut visitor = ModelVariableVisitor::new();
         visitor.visit_expr(expr);
@@ -597,6 +983,7 @@ fn get_map(widget: &Widget, map: &mut PropertyModelMap, is_relm: bool) {
         for var in model_variables {
             let set = map.entry(var).or_default();
             set.insert(Property {
+                branch: branch.cloned(),
                 expr: expr.clone(),
                 is_relm_widget: is_relm,
                 name: name.clone(),
@@ -606,6 +993,13 @@ fn get_map(widget: &Widget, map: &mut PropertyModelMap, is_relm: bool) {
     }
 }
 
+fn is_mut_self_method(sig: &Signature) -> bool {
+    match sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) => receiver.reference.is_some() && receiver.mutability.is_some(),
+        _ => false,
+    }
+}
+
 fn get_return_type(sig: Signature) -> Type {
     if let ReturnType::Type(_, ty) = sig.output {
         *ty
@@ -618,12 +1012,12 @@ fn get_return_type(sig: Signature) -> Type {
     }
 }
 
-fn get_second_param_type(sig: &Signature) -> Type {
-    if let Typed(PatType { ref ty, .. }) = sig.inputs[1] {
-        *ty.clone()
+fn get_second_param_type(sig: &Signature) -> Result<Type> {
+    if let Some(&Typed(PatType { ref ty, .. })) = sig.inputs.iter().nth(1) {
+        Ok(*ty.clone())
     }
     else {
-        panic!("Unexpected `(unknown)`, expecting Typed Type"/*, sig.decl.inputs[1]*/); // TODO
+        Err(Error::new(sig.span(), "expected a message parameter, like `fn update(&mut self, event: Msg)`"))
     }
 }
 
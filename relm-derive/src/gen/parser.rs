@@ -21,10 +21,6 @@
 
 use std::collections::HashSet;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::Read;
-use std::iter::FromIterator;
-use std::str::FromStr;
 use std::sync::Mutex;
 
 use lazy_static::lazy_static;
@@ -32,11 +28,15 @@ use proc_macro2::{Span, TokenTree};
 use quote::{quote, ToTokens};
 use syn::{
     Expr,
+    ExprField,
     ExprMacro,
+    ExprPath,
+    ExprTuple,
     Ident,
     LitStr,
     Macro,
     Pat,
+    PatOr,
     Path,
     Type,
     braced,
@@ -47,10 +47,15 @@ use syn::{
     token,
     Token,
 };
+use syn::Member::Named;
 use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 
+use super::binding;
+use super::include;
+use super::ui;
+
 use self::ChildItem::*;
 use self::EventValue::*;
 use self::EventValueReturn::*;
@@ -126,6 +131,8 @@ pub struct Widget {
     pub name: Ident,
     pub nested_views: HashMap<Ident, Widget>,
     pub parent_id: Option<String>,
+    // The name under which the state of the widget is saved, from the #[persist] attribute.
+    pub persist: Option<LitStr>,
     pub properties: HashMap<Ident, Expr>,
     pub save: bool,
     pub typ: Path,
@@ -150,6 +157,7 @@ impl Widget {
             name,
             nested_views,
             parent_id: None,
+            persist: None,
             properties,
             save: false,
             typ,
@@ -178,6 +186,7 @@ impl Widget {
             name,
             nested_views,
             parent_id: None,
+            persist: None,
             properties,
             save: false,
             typ,
@@ -195,26 +204,134 @@ pub enum EitherWidget {
 
 #[derive(Debug)]
 pub struct GtkWidget {
+    pub condition: Option<Condition>,
     pub construct_properties: HashMap<Ident, Expr>,
     pub events: HashMap<Ident, Event>,
+    pub for_loop: Option<ForLoop>,
+    pub model_bindings: Vec<ModelBinding>,
     pub relm_name: Option<Type>,
+    pub ui_object: Option<UiFileObject>,
+    pub widget_bindings: Vec<WidgetBinding>,
 }
 
 impl GtkWidget {
     fn new() -> Self {
         GtkWidget {
+            condition: None,
             construct_properties: HashMap::new(),
             events: HashMap::new(),
+            for_loop: None,
+            model_bindings: vec![],
             relm_name: None,
+            ui_object: None,
+            widget_bindings: vec![],
         }
     }
 }
 
+/// A widget of a GtkBuilder file, which is taken from the builder instead of being created.
+/// Only the root widget holds the content of the file, to create the builder.
+#[derive(Debug)]
+pub struct UiFileObject {
+    pub id: String,
+    pub ui: Option<String>,
+}
+
+/// A two-way binding, like `text: bind self.model.name`: the property is set when the attribute
+/// of the model changes and the attribute is updated when the user changes the property.
+#[derive(Debug)]
+pub struct ModelBinding {
+    pub expr: Expr,
+    pub field: Ident,
+    pub property: Ident,
+}
+
+/// A binding to the property of another widget, like `sensitive: bind check.active`, created
+/// with a `glib::Binding`.
+#[derive(Debug)]
+pub struct WidgetBinding {
+    pub bidirectional: bool,
+    pub property: Ident,
+    pub source: Ident,
+    pub source_property: Ident,
+    pub transform_from: Option<Expr>,
+    pub transform_to: Option<Expr>,
+}
+
+/// A `if` or `match` block in the view: the widget of the active branch is created inside a
+/// `gtk::Box` and is rebuilt when the result of the condition changes.
+#[derive(Debug)]
+pub struct Condition {
+    pub index: u32,
+    pub kind: ConditionKind,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum ConditionKind {
+    If(Vec<(Expr, Widget)>, Option<Box<Widget>>),
+    Match(Box<Expr>, Vec<MatchArm>),
+}
+
+#[derive(Debug)]
+pub struct MatchArm {
+    pub guard: Option<Expr>,
+    pub pat: Pat,
+    pub widget: Widget,
+}
+
+impl Condition {
+    /// Get the widgets of every branch, in the order of the generated branch indices.
+    pub fn branches(&self) -> Vec<&Widget> {
+        match self.kind {
+            ConditionKind::If(ref branches, ref else_branch) =>
+                branches.iter()
+                    .map(|(_, widget)| widget)
+                    .chain(else_branch.iter().map(|widget| &**widget))
+                    .collect(),
+            ConditionKind::Match(_, ref arms) => arms.iter().map(|arm| &arm.widget).collect(),
+        }
+    }
+
+    /// Get the expressions that decide which branch is active.
+    pub fn exprs(&self) -> Vec<&Expr> {
+        match self.kind {
+            ConditionKind::If(ref branches, _) => branches.iter().map(|(expr, _)| expr).collect(),
+            ConditionKind::Match(ref expr, ref arms) =>
+                Some(&**expr).into_iter()
+                    .chain(arms.iter().filter_map(|arm| arm.guard.as_ref()))
+                    .collect(),
+        }
+    }
+
+    /// Whether no widget is shown when no condition is true.
+    pub fn has_empty_branch(&self) -> bool {
+        match self.kind {
+            ConditionKind::If(_, ref else_branch) => else_branch.is_none(),
+            ConditionKind::Match(_, _) => false,
+        }
+    }
+}
+
+/// A `for` loop in the view: a widget is created inside a `gtk::Box` for every item of the
+/// collection and these widgets are kept in sync, by the key of the items, when the collection is
+/// assigned.
+#[derive(Debug)]
+pub struct ForLoop {
+    pub expr: Expr,
+    pub index: u32,
+    pub key: Expr,
+    pub pat: Pat,
+    pub span: Span,
+    pub widget: Box<Widget>,
+}
+
 #[derive(Debug)]
 pub struct RelmWidget {
     pub events: HashMap<Ident, Vec<Event>>,
     pub gtk_events: HashMap<Ident, Event>,
     pub messages: HashMap<Ident, Expr>,
+    pub named_parameters: Vec<(Ident, Expr)>,
 }
 
 impl RelmWidget {
@@ -223,12 +340,18 @@ impl RelmWidget {
             events: HashMap::new(),
             gtk_events: HashMap::new(),
             messages: HashMap::new(),
+            named_parameters: vec![],
         }
     }
 }
 
 pub struct WidgetList {
     pub widgets: Vec<Widget>,
+    /// The absolute path of the files included in the view.
+    pub included_files: Vec<String>,
+    /// The absolute path of the `.relm` file containing the view, which is reloaded when it
+    /// changes in debug builds.
+    pub view_file: Option<String>,
 }
 
 impl Parse for WidgetList {
@@ -237,16 +360,14 @@ impl Parse for WidgetList {
         if lookahead.peek(LitStr) {
             let literal: LitStr = input.parse()?;
 
-            // TODO: also support glade file.
-            let mut file = File::open(literal.value()).expect("File::open() in parse()");
-            let mut file_content = String::new();
-            file.read_to_string(&mut file_content)
-                .expect("read_to_string() in parse()");
-            let tokens = proc_macro::TokenStream::from_str(&file_content)
-                .expect("convert string to TokenStream");
-            let tokens = respan_with(tokens, literal.span().unwrap());
+            let path = literal.value();
+            if path.ends_with(".ui") || path.ends_with(".glade") {
+                let _comma: Option<Token![,]> = input.parse()?;
+                let items = Punctuated::<UiObjectItem, Token![,]>::parse_terminated(input)?;
+                return parse_ui_file(&literal, items.into_iter().collect());
+            }
 
-            syn::parse(tokens)
+            include::parse_view_file(&literal)
         } else if lookahead.peek(Ident) || lookahead.peek(token::Pound) {
             let mut widgets = vec![];
 
@@ -254,13 +375,113 @@ impl Parse for WidgetList {
                 widgets.push(input.parse()?);
             }
 
-            Ok(WidgetList { widgets })
+            Ok(WidgetList { widgets, included_files: vec![], view_file: None })
         } else {
             Err(lookahead.error())
         }
     }
 }
 
+/*
+ * Syntax:
+ * "file.ui",
+ * object_id {
+ *     property: value,
+ *     event => Msg,
+ * },
+ */
+struct UiObjectItem {
+    id: Ident,
+    widget: Widget,
+}
+
+impl Parse for UiObjectItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let id: Ident = input.parse()?;
+        let typ = Path::from(Ident::new("Widget", id.span()));
+        let widget = GtkWidgetParser::parse(typ, input)?.gtk_widget;
+        Ok(UiObjectItem {
+            id,
+            widget,
+        })
+    }
+}
+
+/// Create a widget for every object of the GtkBuilder file which has an id and add the
+/// properties and events specified for the objects in the `view!` macro.
+/// The objects whose class is not from GTK, like custom widgets, are skipped unless they are used
+/// in the `view!` macro.
+/// The first top-level window of the file is the root widget.
+fn parse_ui_file(literal: &LitStr, items: Vec<UiObjectItem>) -> Result<WidgetList> {
+    let path = literal.value();
+    let (included_file, content) = include::read_file(literal)?;
+    let objects = ui::parse_objects(&content)
+        .map_err(|error| Error::new(literal.span(), format!("invalid GtkBuilder file {}: {}", path, error)))?;
+    let (mut objects, unsupported_objects): (Vec<_>, Vec<_>) = objects.into_iter()
+        .partition(|object| object.class.starts_with("Gtk"));
+    let root_index = objects.iter()
+        .position(|object| object.top_level && (object.class.ends_with("Window") || object.class.ends_with("Dialog")))
+        .or_else(|| objects.iter().position(|object| object.top_level))
+        .ok_or_else(|| Error::new(literal.span(), format!("no top-level GTK object with an id in {}", path)))?;
+    let root = objects.remove(root_index);
+    objects.insert(0, root);
+
+    let mut widgets = vec![];
+    for (index, object) in objects.into_iter().enumerate() {
+        let class = object.class.strip_prefix("Gtk").expect("GTK class");
+        let typ: Path = syn::parse_str(&format!("::gtk::{}", class))
+            .map_err(|_| Error::new(literal.span(), format!("invalid class {}", object.class)))?;
+        let name: Ident = syn::parse_str(&object.id.replace('-', "_"))
+            .map_err(|_| Error::new(literal.span(), format!("the id {} is not a valid identifier", object.id)))?;
+        let mut gtk_widget = GtkWidget::new();
+        gtk_widget.ui_object = Some(UiFileObject {
+            id: object.id,
+            ui: if index == 0 { Some(content.clone()) } else { None },
+        });
+        let mut widget = Widget::new_gtk(gtk_widget, typ, vec![], vec![], HashMap::new(), HashMap::new(),
+            HashMap::new(), HashMap::new());
+        widget.name = Ident::new(&name.to_string(), literal.span());
+        widget.save = true;
+        widgets.push(widget);
+    }
+
+    for item in &items {
+        if let Some(object) = unsupported_objects.iter().find(|object| item.id == object.id.replace('-', "_")) {
+            return Err(Error::new(item.id.span(), format!("unsupported class {} for the object {}: only the GTK classes are supported",
+                object.class, object.id)));
+        }
+    }
+
+    for item in items {
+        let widget = widgets.iter_mut()
+            .find(|widget| widget.name == item.id)
+            .ok_or_else(|| Error::new(item.id.span(), format!("no object with the id {} in {}", item.id, path)))?;
+        merge_ui_object_item(widget, item)?;
+    }
+
+    Ok(WidgetList { widgets, included_files: vec![included_file], view_file: None })
+}
+
+fn merge_ui_object_item(widget: &mut Widget, item: UiObjectItem) -> Result<()> {
+    let UiObjectItem { id, widget: item } = item;
+    if !item.children.is_empty() || !item.init_parameters.is_empty() || !item.child_properties.is_empty() {
+        return Err(Error::new(id.span(),
+            "only properties and events can be added to an object of a GtkBuilder file"));
+    }
+    widget.properties.extend(item.properties);
+    widget.child_events.extend(item.child_events);
+    widget.nested_views.extend(item.nested_views);
+    if let (Gtk(ref mut gtk_widget), Gtk(item_widget)) = (&mut widget.widget, item.widget) {
+        if !item_widget.construct_properties.is_empty() {
+            return Err(Error::new(id.span(), "the objects of a GtkBuilder file are already constructed"));
+        }
+        gtk_widget.events.extend(item_widget.events);
+        gtk_widget.model_bindings.extend(item_widget.model_bindings);
+        gtk_widget.widget_bindings.extend(item_widget.widget_bindings);
+    }
+    Ok(())
+}
+
 enum InitProperties {
     ConstructProperties(HashMap<Ident, Expr>),
     InitParameters(Vec<Expr>),
@@ -332,6 +553,8 @@ enum ChildItem {
     ChildEvent(Ident, Ident, Event),
     ItemChildProperties(ChildProperties),
     ItemEvent(Ident, Event),
+    ItemModelBinding(ModelBinding),
+    ItemWidgetBinding(WidgetBinding),
     ChildWidget(Widget),
     NestedView(Ident, Widget),
     Property(Ident, Value),
@@ -339,21 +562,6 @@ enum ChildItem {
     RelmMsgEvent(Ident, Event),
 }
 
-impl ChildItem {
-    fn unwrap_widget(self) -> Widget {
-        match self {
-            ChildEvent(_, _, _) => panic!("Expected widget, found child event"),
-            ItemEvent(_, _) => panic!("Expected widget, found event"),
-            ItemChildProperties(_) => panic!("Expected widget, found child properties"),
-            NestedView(_, _) => panic!("Expected widget, found nested view"),
-            Property(_, _) => panic!("Expected widget, found property"),
-            RelmMsg(_, _) => panic!("Expected widget, found relm msg"),
-            RelmMsgEvent(_, _) => panic!("Expected widget, found relm msg event"),
-            ChildWidget(widget) => widget,
-        }
-    }
-}
-
 struct AttributeValue {
     value: LitStr,
 }
@@ -391,6 +599,11 @@ impl Parse for Attribute {
         let content;
         let _bracket = bracketed!(content in input);
         let name_values: Punctuated<NameValue, Token![,]> = content.parse_terminated(NameValue::parse)?;
+        for name_value in &name_values {
+            if name_value.name == "style_class" && name_value.value.is_none() {
+                return Err(Error::new(name_value.name.span(), "expected a style class, like `style_class=\"class\"`"));
+            }
+        }
         let name_values = name_values.into_iter()
             .map(|name_value| (name_value.name.to_string(), name_value.value.map(|value| value.value)))
             .collect();
@@ -419,8 +632,7 @@ impl Parse for Attributes {
                     Some(Some(style_class)) => {
                         style_classes.insert(style_class.value());
                     },
-                    Some(None) => panic!("Invalid style_class specification"),
-                    None => name_values.extend(attribute.name_values)
+                    _ => name_values.extend(attribute.name_values),
                 };
             }
             else {
@@ -441,22 +653,6 @@ enum WidgetPath {
     GtkPath(Path),
 }
 
-impl WidgetPath {
-    fn get_relm_path(&self) -> &Path {
-        match *self {
-            RelmPath(ref ident) => ident,
-            GtkPath(_) => panic!("Expected ident"),
-        }
-    }
-
-    fn get_gtk_path(&self) -> &Path {
-        match *self {
-            RelmPath(_) => panic!("Expected path"),
-            GtkPath(ref path) => path,
-        }
-    }
-}
-
 struct WidgetPathParser {
     widget_path: WidgetPath,
 }
@@ -486,7 +682,7 @@ impl Parse for WidgetPathParser {
 }
 
 struct ChildWidgetParser {
-    widget: ChildItem,
+    widget: Widget,
     parent_id: Option<String>,
 }
 
@@ -505,12 +701,12 @@ impl ChildWidgetParser {
         let typ = typ.widget_path;
         let save = attributes.name_values.contains_key("name") || root == Save;
         match typ {
-            RelmPath(_) => {
-                let relm_widget = RelmWidgetParser::parse(typ.get_relm_path().clone(), input)?.relm_widget;
+            RelmPath(path) => {
+                let relm_widget = RelmWidgetParser::parse(path, input)?.relm_widget;
                 Ok(adjust_widget_with_attributes(relm_widget, &attributes.name_values, &attributes.style_classes, save))
             },
-            GtkPath(_) => {
-                let gtk_widget = GtkWidgetParser::parse(typ.get_gtk_path().clone(), input)?.gtk_widget;
+            GtkPath(path) => {
+                let gtk_widget = GtkWidgetParser::parse(path, input)?.gtk_widget;
                 Ok(adjust_widget_with_attributes(gtk_widget, &attributes.name_values, &attributes.style_classes, save))
             },
         }
@@ -518,7 +714,7 @@ impl ChildWidgetParser {
 }
 
 struct GtkWidgetParser {
-    gtk_widget: ChildItem,
+    gtk_widget: Widget,
 }
 
 impl GtkWidgetParser {
@@ -547,10 +743,21 @@ impl GtkWidgetParser {
                     }
                 },
                 ItemEvent(ident, event) => { let _ = gtk_widget.events.insert(ident, event); },
-                ChildWidget(widget) => children.push(widget),
+                ChildWidget(widget) => {
+                    if !is_box(&typ) {
+                        check_no_block(&widget)?;
+                    }
+                    children.push(widget)
+                },
+                ItemModelBinding(model_binding) => {
+                    let _ = properties.insert(model_binding.property.clone(), binding::setter_value(&model_binding));
+                    gtk_widget.model_bindings.push(model_binding);
+                },
+                ItemWidgetBinding(widget_binding) => gtk_widget.widget_bindings.push(widget_binding),
                 NestedView(ident, widget) => { let _ = nested_views.insert(ident, widget); },
                 Property(ident, value) => { let _ = properties.insert(ident, value.value); },
-                RelmMsg(_, _) | RelmMsgEvent(_, _) => panic!("Unexpected relm msg in gtk widget"),
+                RelmMsg(ident, _) | RelmMsgEvent(ident, _) =>
+                    return Err(Error::new(ident.span(), "messages can only be sent to relm widgets")),
             }
         }
         match init_properties {
@@ -559,8 +766,8 @@ impl GtkWidgetParser {
             NoInitParameter => (),
         }
         Ok(GtkWidgetParser {
-            gtk_widget: ChildWidget(Widget::new_gtk(gtk_widget, typ, init_parameters, children, properties,
-                            child_properties, child_events, nested_views)),
+            gtk_widget: Widget::new_gtk(gtk_widget, typ, init_parameters, children, properties,
+                            child_properties, child_events, nested_views),
         })
     }
 }
@@ -573,11 +780,17 @@ impl Parse for ChildRelmItem {
     fn parse(input: ParseStream) -> Result<Self> {
         let parser = input.fork();
         let child_item =
-            if RelmPropertyOrEvent::parse(&parser).is_ok() {
+            if is_condition(input) {
+                ConditionParser::parse(input)?.widget
+            }
+            else if input.peek(Token![for]) {
+                ForLoopParser::parse(input)?.widget
+            }
+            else if RelmPropertyOrEvent::parse(&parser).is_ok() {
                 RelmPropertyOrEvent::parse(input)?.child_item
             }
             else {
-                ChildWidgetParser::parse(DontSave, input)?.widget
+                ChildWidget(ChildWidgetParser::parse(DontSave, input)?.widget)
             };
         Ok(ChildRelmItem {
             child_item,
@@ -586,17 +799,24 @@ impl Parse for ChildRelmItem {
 }
 
 struct RelmWidgetParser {
-    relm_widget: ChildItem,
+    relm_widget: Widget,
 }
 
 impl RelmWidgetParser {
     fn parse(typ: Path, input: ParseStream) -> Result<Self> {
         let lookahead = input.lookahead1();
+        let mut named_parameters = vec![];
         let init_parameters =
             if lookahead.peek(token::Paren) {
                 let content;
                 let _parens = parenthesized!(content in input);
-                Some(ExprList::parse(&content)?.exprs)
+                if content.peek(Ident) && content.peek2(Token![:]) && !content.peek2(Token![::]) {
+                    named_parameters = NamedParameterList::parse(&content)?.parameters;
+                    Some(vec![])
+                }
+                else {
+                    Some(ExprList::parse(&content)?.exprs)
+                }
             }
             else {
                 None
@@ -612,6 +832,7 @@ impl RelmWidgetParser {
 
                 let init_parameters = init_parameters.unwrap_or_default();
                 let mut relm_widget = RelmWidget::new();
+                relm_widget.named_parameters = named_parameters;
                 let mut children = vec![];
                 let mut child_properties = HashMap::new();
                 let mut child_events = HashMap::new();
@@ -622,8 +843,13 @@ impl RelmWidgetParser {
                         ChildEvent(event_name, child_name, event) => {
                             let _ = child_events.insert((child_name, event_name), event);
                         },
-                        ChildWidget(widget) => children.push(widget),
+                        ChildWidget(widget) => {
+                            check_no_block(&widget)?;
+                            children.push(widget)
+                        },
                         ItemEvent(ident, event) => { let _ = relm_widget.gtk_events.insert(ident, event); },
+                        ItemModelBinding(ModelBinding { property, .. }) | ItemWidgetBinding(WidgetBinding { property, .. }) =>
+                            return Err(Error::new(property.span(), "bindings are not supported on relm widgets")),
                         ItemChildProperties(child_props) => {
                             for (key, value) in child_props {
                                 child_properties.insert(key, value);
@@ -638,13 +864,15 @@ impl RelmWidgetParser {
                         },
                     }
                 }
-                ChildWidget(Widget::new_relm(relm_widget, typ, init_parameters, children, properties,
-                    child_properties, child_events, nested_views))
+                Widget::new_relm(relm_widget, typ, init_parameters, children, properties,
+                    child_properties, child_events, nested_views)
             }
             else {
                 let init_parameters = init_parameters.unwrap_or_else(Vec::new);
-                ChildWidget(Widget::new_relm(RelmWidget::new(), typ, init_parameters, vec![], HashMap::new(),
-                    HashMap::new(), HashMap::new(), HashMap::new()))
+                let mut relm_widget = RelmWidget::new();
+                relm_widget.named_parameters = named_parameters;
+                Widget::new_relm(relm_widget, typ, init_parameters, vec![], HashMap::new(),
+                    HashMap::new(), HashMap::new(), HashMap::new())
             };
         Ok(RelmWidgetParser {
             relm_widget,
@@ -669,7 +897,7 @@ impl Parse for RelmPropertyOrEvent {
                     // Uppercase is a msg to send.
                     match result {
                         Property(ident, value) => RelmMsg(ident, value),
-                        _ => panic!("Expecting property"),
+                        _ => return Err(Error::new(ident.span(), "expected a message parameter")),
                     }
                 }
                 else {
@@ -723,7 +951,12 @@ impl Parse for GtkChildPropertyOrEvent {
         let child_item =
             if lookahead.peek(Token![:]) {
                 let _colon: Token![:] = input.parse()?;
-                ValueOrChildProperties::parse(input, &ident)?.child_item
+                if is_binding(input) {
+                    BindingParser::parse(input, ident)?.child_item
+                }
+                else {
+                    ValueOrChildProperties::parse(input, &ident)?.child_item
+                }
             }
             else if lookahead.peek(Token![.]) {
                 let _colon: Token![.] = input.parse()?;
@@ -748,6 +981,117 @@ impl Parse for GtkChildPropertyOrEvent {
     }
 }
 
+/// Check if a property value is a binding, like `bind self.model.name` or `bind check.active`.
+fn is_binding(input: ParseStream) -> bool {
+    let parser = input.fork();
+    Tag::parse(&parser, "bind").is_ok() &&
+        (parser.peek(Token![self]) || parser.peek(Ident) || parser.peek(token::Paren))
+}
+
+/*
+ * Syntax:
+ * bind self.model.attribute
+ * bind widget.property
+ * bind widget.property => transform_to
+ * bind(bidirectional) widget.property
+ * bind(bidirectional) widget.property => (transform_to, transform_from)
+ */
+struct BindingParser {
+    child_item: ChildItem,
+}
+
+impl BindingParser {
+    fn parse(input: ParseStream, property: Ident) -> Result<Self> {
+        Tag::parse(input, "bind")?;
+        let mut bidirectional = false;
+        if input.peek(token::Paren) {
+            let content;
+            let _parens = parenthesized!(content in input);
+            Tag::parse(&content, "bidirectional")?;
+            bidirectional = true;
+        }
+        let expr: Expr = input.parse()?;
+        let transforms =
+            if input.peek(Token![=>]) {
+                let _arrow: Token![=>] = input.parse()?;
+                Some(input.parse::<Expr>()?)
+            }
+            else {
+                None
+            };
+
+        if let Some(field) = bound_model_field(&expr) {
+            if bidirectional || transforms.is_some() {
+                return Err(Error::new(expr.span(),
+                    "a binding to the model is always bidirectional and cannot have a transform"));
+            }
+            if !binding::is_supported(&property) {
+                return Err(Error::new(property.span(),
+                    format!("cannot bind the property {}: only {} are supported", property,
+                        binding::SUPPORTED_PROPERTIES.join(", "))));
+            }
+            return Ok(BindingParser {
+                child_item: ItemModelBinding(ModelBinding {
+                    field: field.clone(),
+                    expr,
+                    property,
+                }),
+            });
+        }
+
+        let (source, source_property) = bound_widget_property(&expr)
+            .ok_or_else(|| Error::new(expr.span(),
+                "expected an attribute of the model, like `self.model.name`, or a property of a widget, like `check.active`"))?;
+        let (transform_to, transform_from) =
+            match transforms {
+                Some(Expr::Tuple(ExprTuple { ref elems, .. })) if bidirectional && elems.len() == 2 =>
+                    (Some(elems[0].clone()), Some(elems[1].clone())),
+                Some(transform) if bidirectional =>
+                    return Err(Error::new(transform.span(),
+                        "expected a tuple of two transforms for a bidirectional binding: (transform_to, transform_from)")),
+                transform => (transform, None),
+            };
+        Ok(BindingParser {
+            child_item: ItemWidgetBinding(WidgetBinding {
+                bidirectional,
+                property,
+                source,
+                source_property,
+                transform_from,
+                transform_to,
+            }),
+        })
+    }
+}
+
+/// Get the widget name and the property from an expression like `check.active`.
+fn bound_widget_property(expr: &Expr) -> Option<(Ident, Ident)> {
+    if let Expr::Field(ExprField { ref base, member: Named(ref property), .. }) = *expr {
+        if let Expr::Path(ExprPath { ref path, .. }) = **base {
+            if let Some(widget) = path.get_ident() {
+                return Some((widget.clone(), property.clone()));
+            }
+        }
+    }
+    None
+}
+
+/// Get the attribute of the model from an expression like `self.model.name` or
+/// `self.model.person.name`.
+fn bound_model_field(expr: &Expr) -> Option<&Ident> {
+    if let Expr::Field(ExprField { ref base, member: Named(ref ident), .. }) = *expr {
+        if let Expr::Field(ExprField { base: ref self_expr, member: Named(ref model), .. }) = **base {
+            if let Expr::Path(ExprPath { ref path, .. }) = **self_expr {
+                if path.is_ident(&dummy_ident("self")) && model == "model" {
+                    return Some(ident);
+                }
+            }
+        }
+        return bound_model_field(base);
+    }
+    None
+}
+
 struct ValueOrChildProperties {
     child_item: ChildItem,
 }
@@ -932,6 +1276,30 @@ impl Parse for ExprList {
     }
 }
 
+struct NamedParameterList {
+    parameters: Vec<(Ident, Expr)>,
+}
+
+impl Parse for NamedParameterList {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let parameters: Punctuated<(Ident, Expr), Token![,]> = input.parse_terminated(|input| {
+            let ident: Ident = input.parse()?;
+            let _colon: Token![:] = input.parse()?;
+            let value: Expr = input.parse()?;
+            Ok((ident, value))
+        })?;
+        let mut names = HashSet::new();
+        for (ident, _) in &parameters {
+            if !names.insert(ident.to_string()) {
+                return Err(Error::new(ident.span(), format!("duplicate parameter `{}`", ident)));
+            }
+        }
+        Ok(NamedParameterList {
+            parameters: parameters.into_iter().collect(),
+        })
+    }
+}
+
 struct IdentList {
     idents: Vec<Ident>,
 }
@@ -998,7 +1366,10 @@ fn is_property_or_event(input: &ParseStream) -> bool {
             }
         }
     }
-    let _ident: Ident = input.parse().expect("should be an ident");
+    if input.parse::<Ident>().is_err() {
+        // Let the widget parser report the error.
+        return false;
+    }
     if input.peek(token::Brace) {
         // Only a widget can have an ident followed by { .
         return false;
@@ -1018,7 +1389,9 @@ fn is_property_or_event(input: &ParseStream) -> bool {
         let _content;
         let _parens = parenthesized!(_content in input);
     }};
-    result.expect("parse parenthesis");
+    if result.is_err() {
+        return false;
+    }
     if input.peek(token::Brace) {
         // Only a widget can have an ident followed by { .
         return false;
@@ -1045,7 +1418,17 @@ struct ChildGtkItem {
 
 impl Parse for ChildGtkItem {
     fn parse(input: ParseStream) -> Result<Self> {
-        if is_property_or_event(&input) {
+        if is_condition(input) {
+            Ok(ChildGtkItem {
+                item: ConditionParser::parse(input)?.widget,
+            })
+        }
+        else if input.peek(Token![for]) {
+            Ok(ChildGtkItem {
+                item: ForLoopParser::parse(input)?.widget,
+            })
+        }
+        else if is_property_or_event(&input) {
             let item: GtkChildPropertyOrEvent = input.parse()?;
             Ok(ChildGtkItem {
                 item: item.child_item,
@@ -1053,12 +1436,237 @@ impl Parse for ChildGtkItem {
         }
         else {
             Ok(ChildGtkItem {
-                item: ChildWidgetParser::parse(DontSave, input)?.widget
+                item: ChildWidget(ChildWidgetParser::parse(DontSave, input)?.widget)
             })
         }
     }
 }
 
+fn is_condition(input: ParseStream) -> bool {
+    input.peek(Token![if]) || input.peek(Token![match])
+}
+
+fn is_box(typ: &Path) -> bool {
+    typ.segments.last().is_some_and(|segment| segment.ident == "Box")
+}
+
+/// The widgets of a condition and of a loop are added to a `gtk::Box` which would be a wrong child
+/// in the other containers, like a `gtk::ListBox` or a `gtk::Grid`.
+fn check_no_block(widget: &Widget) -> Result<()> {
+    if let Gtk(ref gtk_widget) = widget.widget {
+        let span = gtk_widget.condition.as_ref().map(|condition| condition.span)
+            .or_else(|| gtk_widget.for_loop.as_ref().map(|for_loop| for_loop.span));
+        if let Some(span) = span {
+            return Err(Error::new(span, "conditions and loops can only be used inside a gtk::Box"));
+        }
+    }
+    Ok(())
+}
+
+struct ConditionParser {
+    widget: ChildItem,
+}
+
+/*
+ * First tokens:
+ * * if
+ * * match
+ */
+impl Parse for ConditionParser {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let span = input.span();
+        let lookahead = input.lookahead1();
+        let kind =
+            if lookahead.peek(Token![if]) {
+                let mut branches = vec![];
+                let mut else_branch = None;
+                loop {
+                    let _if: Token![if] = input.parse()?;
+                    let condition = Expr::parse_without_eager_brace(input)?;
+                    let content;
+                    let _brace = braced!(content in input);
+                    branches.push((condition, BranchWidget::parse(&content)?.widget));
+                    if input.peek(Token![else]) {
+                        let _else: Token![else] = input.parse()?;
+                        if !input.peek(Token![if]) {
+                            let content;
+                            let _brace = braced!(content in input);
+                            else_branch = Some(Box::new(BranchWidget::parse(&content)?.widget));
+                            break;
+                        }
+                    }
+                    else {
+                        break;
+                    }
+                }
+                ConditionKind::If(branches, else_branch)
+            }
+            else if lookahead.peek(Token![match]) {
+                let _match: Token![match] = input.parse()?;
+                let expr = Expr::parse_without_eager_brace(input)?;
+                let content;
+                let _brace = braced!(content in input);
+                let mut arms = vec![];
+                while !content.is_empty() {
+                    arms.push(MatchArm::parse(&content)?);
+                }
+                ConditionKind::Match(Box::new(expr), arms)
+            }
+            else {
+                return Err(lookahead.error());
+            };
+        let index = gen_index("__relm_condition");
+        let name = Ident::new(&format!("__relm_condition{}", index), Span::call_site());
+        let typ: Path = parse(quote! { ::gtk::Box }.into()).expect("gtk::Box path");
+        let mut gtk_widget = GtkWidget::new();
+        gtk_widget.condition = Some(Condition {
+            index,
+            kind,
+            span,
+        });
+        let mut widget = Widget::new_gtk(gtk_widget, typ, vec![], vec![], HashMap::new(), HashMap::new(),
+            HashMap::new(), HashMap::new());
+        widget.name = name;
+        widget.save = true;
+        Ok(ConditionParser {
+            widget: ChildWidget(widget),
+        })
+    }
+}
+
+struct ForLoopParser {
+    widget: ChildItem,
+}
+
+/*
+ * First token:
+ * * for
+ *
+ * for pat in expr; key = expr { widget }
+ */
+impl Parse for ForLoopParser {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let span = input.span();
+        let _for: Token![for] = input.parse()?;
+        let pat = Pat::parse(input)?;
+        let _in: Token![in] = input.parse()?;
+        let expr = Expr::parse_without_eager_brace(input)?;
+        if !input.peek(Token![;]) {
+            return Err(input.error("expected the key of the items, like `for item in items; key = item.id`"));
+        }
+        let _semi: Token![;] = input.parse()?;
+        let key_ident: Ident = input.parse()?;
+        if key_ident != "key" {
+            return Err(Error::new(key_ident.span(), "expected `key`"));
+        }
+        let _equal: Token![=] = input.parse()?;
+        let key = Expr::parse_without_eager_brace(input)?;
+        let content;
+        let _brace = braced!(content in input);
+        let item_widget = BranchWidget::parse(&content)?.widget;
+        let index = gen_index("__relm_loop");
+        let name = Ident::new(&format!("__relm_loop{}", index), Span::call_site());
+        let typ: Path = parse(quote! { ::gtk::Box }.into()).expect("gtk::Box path");
+        let mut gtk_widget = GtkWidget::new();
+        gtk_widget.for_loop = Some(ForLoop {
+            expr,
+            index,
+            key,
+            pat,
+            span,
+            widget: Box::new(item_widget),
+        });
+        let mut widget = Widget::new_gtk(gtk_widget, typ, vec![], vec![], HashMap::new(), HashMap::new(),
+            HashMap::new(), HashMap::new());
+        widget.name = name;
+        widget.save = true;
+        Ok(ForLoopParser {
+            widget: ChildWidget(widget),
+        })
+    }
+}
+
+impl Parse for MatchArm {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let leading_vert: Option<Token![|]> = input.parse()?;
+        let mut cases = Punctuated::new();
+        cases.push_value(Pat::parse(input)?);
+        while input.peek(Token![|]) {
+            let punct: Token![|] = input.parse()?;
+            cases.push_punct(punct);
+            cases.push_value(Pat::parse(input)?);
+        }
+        let pat =
+            if cases.len() == 1 && leading_vert.is_none() {
+                cases.into_iter().next().expect("pattern")
+            }
+            else {
+                Pat::Or(PatOr {
+                    attrs: vec![],
+                    leading_vert,
+                    cases,
+                })
+            };
+        let guard =
+            if input.peek(Token![if]) {
+                let _if: Token![if] = input.parse()?;
+                Some(input.parse()?)
+            }
+            else {
+                None
+            };
+        let _arrow: Token![=>] = input.parse()?;
+        let widget = ChildWidgetParser::parse(DontSave, input)?.widget;
+        check_no_condition(&widget)?;
+        let _comma: Option<Token![,]> = input.parse()?;
+        Ok(MatchArm {
+            guard,
+            pat,
+            widget,
+        })
+    }
+}
+
+struct BranchWidget {
+    widget: Widget,
+}
+
+impl Parse for BranchWidget {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let widget = ChildWidgetParser::parse(DontSave, input)?.widget;
+        check_no_condition(&widget)?;
+        let _comma: Option<Token![,]> = input.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("a branch of a condition or the body of a loop must contain exactly one widget"));
+        }
+        Ok(BranchWidget {
+            widget,
+        })
+    }
+}
+
+// TODO: support nested conditions and loops.
+fn check_no_condition(widget: &Widget) -> Result<()> {
+    if let Gtk(GtkWidget { ref condition, ref for_loop, ref model_bindings, ref widget_bindings, .. }) = widget.widget {
+        if condition.is_some() || for_loop.is_some() {
+            return Err(Error::new(widget.typ.span(),
+                "conditions and loops cannot be nested inside another condition or loop"));
+        }
+        if let Some(model_binding) = model_bindings.first() {
+            return Err(Error::new(model_binding.property.span(),
+                "bindings to the model are not supported inside a condition or a loop"));
+        }
+        if let Some(widget_binding) = widget_bindings.first() {
+            return Err(Error::new(widget_binding.property.span(),
+                "bindings to another widget are not supported inside a condition or a loop"));
+        }
+    }
+    for child in widget.children.iter().chain(widget.nested_views.values()) {
+        check_no_condition(child)?;
+    }
+    Ok(())
+}
+
 struct ChildProp {
     name: Ident,
     value: Value,
@@ -1103,7 +1711,7 @@ impl Parse for Widget {
         let child_widget = ChildWidgetParser::parse(Save, input)?;
         let _token: Option<Token![,]> = input.parse().ok();
 
-        let mut widget = child_widget.widget.unwrap_widget();
+        let mut widget = child_widget.widget;
         widget.parent_id = child_widget.parent_id;
         Ok(widget)
     }
@@ -1118,12 +1726,17 @@ fn gen_widget_name(path: &Path) -> Ident {
         else {
             name.to_lowercase()
         };
-    let mut hashmap = NAMES_INDEX.lock().expect("lock() in gen_widget_name()");
-    let index = hashmap.entry(name.clone()).or_insert(0);
-    *index += 1;
+    let index = gen_index(&name);
     Ident::new(&format!("{}{}", name, index), path.span())
 }
 
+fn gen_index(name: &str) -> u32 {
+    let mut hashmap = NAMES_INDEX.lock().expect("lock() in gen_index()");
+    let index = hashmap.entry(name.to_string()).or_insert(0);
+    *index += 1;
+    *index
+}
+
 fn path_to_string(path: &Path) -> String {
     let mut string = String::new();
     for segment in &path.segments {
@@ -1132,54 +1745,30 @@ fn path_to_string(path: &Path) -> String {
     string
 }
 
-fn adjust_widget_with_attributes(mut widget: ChildItem, attributes: &HashMap<String, Option<LitStr>>, style_classes: &HashSet<String>, save: bool)
+fn adjust_widget_with_attributes(mut widget: Widget, attributes: &HashMap<String, Option<LitStr>>, style_classes: &HashSet<String>, save: bool)
     -> ChildWidgetParser
 {
-    let parent_id;
-    match widget {
-        ChildWidget(ref mut widget) => {
-            widget.save = save;
-            let container_type = attributes.get("container")
-                .map(|typ| typ.as_ref().map(|lit| lit.value()));
-            let name = attributes.get("name").and_then(|name| name.clone());
-            if let Some(name) = name {
-                widget.name = Ident::new(&name.value(), name.span());
-            }
-            // style_class attribute
-            for style_class in style_classes {
-                widget.style_classes.push((*style_class).clone());
-            }
-            widget.is_container = !widget.children.is_empty();
-            widget.container_type = container_type;
-            parent_id = attributes.get("parent").and_then(|opt_str| opt_str.as_ref().map(|lit| lit.value()));
-        },
-        _ => panic!("Expecting widget"),
+    widget.save = save;
+    let container_type = attributes.get("container")
+        .map(|typ| typ.as_ref().map(|lit| lit.value()));
+    let name = attributes.get("name").and_then(|name| name.clone());
+    if let Some(name) = name {
+        widget.name = Ident::new(&name.value(), name.span());
     }
+    // style_class attribute
+    for style_class in style_classes {
+        widget.style_classes.push((*style_class).clone());
+    }
+    widget.persist = attributes.get("persist").and_then(|name| name.clone());
+    widget.is_container = !widget.children.is_empty();
+    widget.container_type = container_type;
+    let parent_id = attributes.get("parent").and_then(|opt_str| opt_str.as_ref().map(|lit| lit.value()));
     ChildWidgetParser {
         widget,
         parent_id,
     }
 }
 
-pub fn respan_with(tokens: proc_macro::TokenStream, span: proc_macro::Span) -> proc_macro::TokenStream {
-    let mut result = vec![];
-    for mut token in tokens {
-        match token {
-            proc_macro::TokenTree::Group(group) => {
-                let new_tokens = respan_with(group.stream(), span);
-                let mut res = proc_macro::TokenTree::Group(proc_macro::Group::new(group.delimiter(), new_tokens));
-                res.set_span(span);
-                result.push(res);
-            },
-            _ => {
-                token.set_span(span);
-                result.push(token);
-            }
-        }
-    }
-    FromIterator::from_iter(result)
-}
-
 pub fn dummy_ident(ident: &str) -> Ident {
     Ident::new(ident, Span::call_site())
 }
@@ -0,0 +1,146 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Parsing and code generation for the `settings!` macro, which binds attributes of the model to
+//! persistent settings, like:
+//!
+//! ```ignore
+//! settings! {
+//!     self.model.settings,
+//!     dark_theme: "dark-theme",
+//!     font_size: "font-size" => FontSizeChanged,
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Expr, Ident, LitStr, Token};
+use syn::fold::Fold;
+use syn::parse::{Parse, ParseStream, Result};
+
+use super::SettingModelMap;
+use super::adder::SettingBinding;
+use super::transformer::Transformer;
+use super::MODEL_IDENT;
+
+#[derive(Debug)]
+pub struct SettingList {
+    settings: Vec<Setting>,
+    store: Expr,
+}
+
+#[derive(Debug)]
+struct Setting {
+    field: Ident,
+    key: LitStr,
+    // The message sent when the setting is changed outside of the component.
+    msg: Option<Expr>,
+}
+
+impl SettingList {
+    fn store(&self) -> Expr {
+        let mut remover = Transformer::new(MODEL_IDENT);
+        remover.fold_expr(self.store.clone())
+    }
+}
+
+impl Parse for SettingList {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let store = input.parse()?;
+        let mut settings = vec![];
+        while !input.is_empty() {
+            let _: Token![,] = input.parse()?;
+            if input.is_empty() {
+                break;
+            }
+            settings.push(input.parse()?);
+        }
+        Ok(SettingList {
+            settings,
+            store,
+        })
+    }
+}
+
+impl Parse for Setting {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let field = input.parse()?;
+        let _: Token![:] = input.parse()?;
+        let key = input.parse()?;
+        let msg =
+            if input.peek(Token![=>]) {
+                let _: Token![=>] = input.parse()?;
+                Some(input.parse()?)
+            }
+            else {
+                None
+            };
+        Ok(Setting {
+            field,
+            key,
+            msg,
+        })
+    }
+}
+
+/// Generate the code overriding the attributes of the model created by `model()` with the values
+/// of the settings.
+pub fn gen_load(settings: &SettingList) -> TokenStream {
+    let store = settings.store();
+    let loads = settings.settings.iter().map(|setting| {
+        let field = &setting.field;
+        let key = &setting.key;
+        quote_spanned! { key.span() =>
+            if let ::std::option::Option::Some(__relm_value) = ::relm::Settings::get(&(#store), #key) {
+                __relm_model.#field = __relm_value;
+            }
+        }
+    });
+    quote! {
+        #(#loads)*
+    }
+}
+
+/// Generate the code sending the messages when the settings are changed outside of the
+/// component, in the `view()` function.
+pub fn gen_connect(settings: &SettingList) -> TokenStream {
+    let store = settings.store();
+    let connects = settings.settings.iter()
+        .filter_map(|setting| setting.msg.as_ref().map(|msg| (&setting.key, msg)))
+        .map(|(key, msg)| quote_spanned! { key.span() =>
+            ::relm::Settings::connect_changed(&(#store), #key, relm.stream(), #msg);
+        });
+    quote! {
+        #(#connects)*
+    }
+}
+
+/// Map the attributes of the model to the settings they are written to.
+pub fn get_settings_model_map(settings: &SettingList, map: &mut SettingModelMap) {
+    for setting in &settings.settings {
+        let set = map.entry(setting.field.clone()).or_default();
+        set.insert(SettingBinding {
+            field: setting.field.clone(),
+            key: setting.key.clone(),
+            store: settings.store.clone(),
+        });
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+//! Minimal reader of the GtkBuilder files (`.ui` or `.glade`), to get the objects used by the
+//! `view!` macro.
+
+/// An object of the file, with its id.
+#[derive(Debug)]
+pub struct UiObject {
+    pub class: String,
+    pub id: String,
+    pub top_level: bool,
+}
+
+/// Get the objects which have an id, in the order of the file.
+pub fn parse_objects(content: &str) -> Result<Vec<UiObject>, String> {
+    let mut objects = vec![];
+    let mut elements: Vec<String> = vec![];
+    let mut rest = content;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if rest.starts_with("<!--") {
+            rest = skip_after(rest, "-->")?;
+        }
+        else if rest.starts_with("<![CDATA[") {
+            rest = skip_after(rest, "]]>")?;
+        }
+        else if rest.starts_with("<?") || rest.starts_with("<!") {
+            rest = skip_after(rest, ">")?;
+        }
+        else if let Some(end_tag) = rest.strip_prefix("</") {
+            let end = end_tag.find('>').ok_or("unterminated end tag")?;
+            let name = end_tag[..end].trim();
+            if elements.pop().as_deref() != Some(name) {
+                return Err(format!("unexpected end tag </{}>", name));
+            }
+            rest = &end_tag[end + 1..];
+        }
+        else {
+            let end = tag_end(rest).ok_or("unterminated tag")?;
+            let tag = &rest[1..end];
+            let (tag, self_closing) =
+                match tag.strip_suffix('/') {
+                    Some(tag) => (tag, true),
+                    None => (tag, false),
+                };
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            let name = &tag[..name_end];
+            let attributes = parse_attributes(&tag[name_end..])?;
+            match name {
+                "object" => {
+                    let top_level = elements.last().map(String::as_str) == Some("interface");
+                    if let Some(id) = attribute(&attributes, "id") {
+                        let class = attribute(&attributes, "class")
+                            .ok_or_else(|| format!("missing class for the object {}", id))?;
+                        objects.push(UiObject {
+                            class: class.to_string(),
+                            id: id.to_string(),
+                            top_level,
+                        });
+                    }
+                },
+                "template" => return Err("templates are not supported".to_string()),
+                _ => (),
+            }
+            if !self_closing {
+                elements.push(name.to_string());
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+    if let Some(element) = elements.last() {
+        return Err(format!("unclosed element <{}>", element));
+    }
+    Ok(objects)
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes.iter()
+        .find(|(attribute, _)| attribute == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn parse_attributes(mut input: &str) -> Result<Vec<(String, String)>, String> {
+    let mut attributes = vec![];
+    loop {
+        input = input.trim_start();
+        if input.is_empty() {
+            return Ok(attributes);
+        }
+        let equal = input.find('=').ok_or("expected = in attribute")?;
+        let name = input[..equal].trim().to_string();
+        input = input[equal + 1..].trim_start();
+        let quote = input.chars().next().filter(|&char| char == '"' || char == '\'')
+            .ok_or_else(|| format!("expected quoted value for the attribute {}", name))?;
+        input = &input[1..];
+        let end = input.find(quote).ok_or("unterminated attribute value")?;
+        attributes.push((name, unescape(&input[..end])));
+        input = &input[end + 1..];
+    }
+}
+
+/// Get the position of the `>` ending the tag, ignoring the ones in the attribute values.
+fn tag_end(input: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, char) in input.char_indices() {
+        match (quote, char) {
+            (None, '"') | (None, '\'') => quote = Some(char),
+            (Some(current), _) if current == char => quote = None,
+            (None, '>') => return Some(index),
+            _ => (),
+        }
+    }
+    None
+}
+
+fn skip_after<'a>(input: &'a str, pattern: &str) -> Result<&'a str, String> {
+    input.find(pattern)
+        .map(|index| &input[index + pattern.len()..])
+        .ok_or_else(|| format!("expected {}", pattern))
+}
+
+fn unescape(value: &str) -> String {
+    value.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
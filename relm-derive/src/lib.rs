@@ -40,38 +40,60 @@ use syn::{
     TypeParam,
     parse,
 };
+use syn::parse::{Error, Result};
 use syn::spanned::Spanned;
 
 use gen::{gen_widget, gen_where_clause, parser::dummy_ident};
 
 #[proc_macro_derive(Msg)]
 pub fn msg(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let ast: Item = parse(input).expect("msg > parse failed");
-    let gen = impl_msg(&ast, Ident::new("relm", ast.span()));
+    let ast: Item =
+        match parse(input) {
+            Ok(ast) => ast,
+            Err(error) => return error.to_compile_error().into(),
+        };
+    let gen = impl_msg(&ast, Ident::new("relm", ast.span()))
+        .unwrap_or_else(|error| error.to_compile_error());
     gen.into()
 }
 
 #[proc_macro_attribute]
-pub fn widget(_attributes: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let ast: Item = parse(input).expect("widget.parse failed");
+pub fn widget(attributes: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // With #[widget(dump)], the generated code is written to a file.
+    let dump =
+        if attributes.is_empty() {
+            false
+        }
+        else {
+            match parse::<Ident>(attributes) {
+                Ok(ident) if ident == "dump" => true,
+                Ok(ident) => return Error::new(ident.span(), "unknown argument, expected `dump`").to_compile_error().into(),
+                Err(error) => return error.to_compile_error().into(),
+            }
+        };
+    let ast: Item =
+        match parse(input) {
+            Ok(ast) => ast,
+            Err(error) => return error.to_compile_error().into(),
+        };
     let tokens = quote! {
         #ast
     };
-    let expanded = gen_widget(tokens);
+    let expanded = gen_widget(tokens, dump);
     expanded.into()
 }
 
-fn impl_msg(ast: &Item, krate: Ident) -> TokenStream {
-    let display = derive_display_variant(ast, &krate);
-    let into_option = derive_into_option(ast, &krate);
+fn impl_msg(ast: &Item, krate: Ident) -> Result<TokenStream> {
+    let display = derive_display_variant(ast, &krate)?;
+    let into_option = derive_into_option(ast, &krate)?;
 
-    quote! {
+    Ok(quote! {
         #display
         #into_option
-    }
+    })
 }
 
-fn derive_display_variant(ast: &Item, krate: &Ident) -> TokenStream {
+fn derive_display_variant(ast: &Item, krate: &Ident) -> Result<TokenStream> {
     if let Item::Enum(ref enum_item) = *ast {
         let generics = &enum_item.generics;
         let name = &enum_item.ident;
@@ -93,7 +115,7 @@ fn derive_display_variant(ast: &Item, krate: &Ident) -> TokenStream {
         });
         let where_clause = gen_where_clause(generics);
 
-        quote_spanned! { krate.span() =>
+        Ok(quote_spanned! { krate.span() =>
             impl #generics ::#krate::DisplayVariant for #typ #where_clause {
                 #[allow(unused_qualifications)]
                 fn display_variant(&self) -> &'static str {
@@ -102,14 +124,14 @@ fn derive_display_variant(ast: &Item, krate: &Ident) -> TokenStream {
                     }
                 }
             }
-        }
+        })
     }
     else {
-        panic!("Expected enum");
+        Err(not_an_enum(ast))
     }
 }
 
-fn derive_into_option(ast: &Item, krate: &Ident) -> TokenStream {
+fn derive_into_option(ast: &Item, krate: &Ident) -> Result<TokenStream> {
     if let Item::Enum(ref enum_item) = *ast {
         let generics = &enum_item.generics;
         let name = &enum_item.ident;
@@ -119,19 +141,29 @@ fn derive_into_option(ast: &Item, krate: &Ident) -> TokenStream {
         };
         let where_clause = gen_where_clause(generics);
 
-        quote_spanned! { krate.span() =>
+        Ok(quote_spanned! { krate.span() =>
             impl #generics ::#krate::IntoOption<#typ> for #typ #where_clause {
                 fn into_option(self) -> Option<#typ> {
                     Some(self)
                 }
             }
-        }
+        })
     }
     else {
-        panic!("Expecting enum");
+        Err(not_an_enum(ast))
     }
 }
 
+fn not_an_enum(ast: &Item) -> Error {
+    let span =
+        match *ast {
+            Item::Struct(ref item) => item.struct_token.span,
+            Item::Union(ref item) => item.union_token.span,
+            _ => ast.span(),
+        };
+    Error::new(span, "#[derive(Msg)] can only be used on an enum")
+}
+
 fn remove_generic_bounds(generics: &Generics) -> Generics {
     let mut generics = generics.clone();
     for param in generics.params.iter_mut() {
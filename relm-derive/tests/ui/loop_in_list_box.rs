@@ -0,0 +1,31 @@
+#![allow(unused_imports)]
+
+use relm::Widget;
+use relm_derive::widget;
+
+pub struct Model {
+    items: Vec<String>,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            items: vec![],
+        }
+    }
+
+    fn update(&mut self, _: ()) {}
+
+    view! {
+        gtk::ListBox {
+            for item in &self.model.items; key = item.clone() {
+                gtk::Label {
+                    text: item,
+                }
+            },
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+#![allow(unused_imports)]
+
+use relm::Widget;
+use relm_derive::widget;
+
+pub struct Model {
+    items: Vec<String>,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            items: vec![],
+        }
+    }
+
+    fn update(&mut self, _: ()) {}
+
+    view! {
+        gtk::Box {
+            for item in &self.model.items {
+                gtk::Label {
+                    text: item,
+                }
+            },
+        }
+    }
+}
+
+fn main() {}
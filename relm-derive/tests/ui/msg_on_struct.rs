@@ -0,0 +1,8 @@
+use relm_derive::Msg;
+
+#[derive(Msg)]
+struct Msg {
+    value: i32,
+}
+
+fn main() {}
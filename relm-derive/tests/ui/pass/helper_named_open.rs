@@ -0,0 +1,40 @@
+use gtk::prelude::WidgetExt;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+pub struct Model {
+    opened: bool,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Open(String),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            opened: false,
+        }
+    }
+
+    // Not the signature of Widget::open(), so this stays a method of the widget.
+    fn open(path: &str) -> bool {
+        !path.is_empty()
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Msg::Open(path) => self.model.opened = Self::open(&path),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            visible: self.model.opened,
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,73 @@
+#![allow(unused_imports)]
+
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::CounterMsg::SetValue;
+
+pub struct Model {
+    value: i32,
+}
+
+#[derive(Msg)]
+pub enum CounterMsg {
+    SetValue(i32),
+}
+
+#[widget]
+impl Widget for Counter {
+    fn model() -> Model {
+        Model {
+            value: 0,
+        }
+    }
+
+    fn update(&mut self, event: CounterMsg) {
+        match event {
+            CounterMsg::SetValue(value) => self.model.value = value,
+        }
+    }
+
+    view! {
+        gtk::Label {
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Changed(i32),
+    Reset(i32),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            value: 0,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Msg::Changed(value) => if value != self.model.value { self.model.value = value },
+            Msg::Reset(value) if value != self.model.value => self.model.value = value,
+            Msg::Reset(_) => (),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            Counter {
+                SetValue: self.model.value,
+                SetValue(value) => Msg::Changed(value),
+            },
+            Counter {
+                SetValue: self.model.value,
+                SetValue(value) => Msg::Reset(value),
+            },
+        }
+    }
+}
+
+fn main() {}
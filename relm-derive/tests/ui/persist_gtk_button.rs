@@ -0,0 +1,25 @@
+#![allow(unused_imports)]
+
+use relm::Widget;
+use relm_derive::widget;
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, _: ()) {}
+
+    view! {
+        gtk::Window {
+            #[persist = "paned"]
+            gtk::Paned {
+                #[persist = "button"]
+                gtk::Button {
+                },
+            },
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+#![allow(unused_imports)]
+
+use relm::Widget;
+use relm_derive::widget;
+
+#[widget]
+impl Widget for Counter {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, _: ()) {}
+
+    view! {
+        gtk::Label {
+        }
+    }
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, _: ()) {}
+
+    view! {
+        gtk::Window {
+            #[persist = "counter"]
+            Counter,
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+#![allow(unused_imports)]
+
+use relm::Widget;
+use relm_derive::widget;
+
+pub struct Model {
+    active: bool,
+}
+
+#[widget]
+impl Widget for Foo {
+    fn model() -> Model {
+        Model {
+            active: false,
+        }
+    }
+
+    fn update(&mut self, _: bool) {}
+
+    actions! {
+        toggle = self.model.active => |active| active,
+    }
+
+    view! {
+        gtk::Window {
+        }
+    }
+}
+
+fn main() {}
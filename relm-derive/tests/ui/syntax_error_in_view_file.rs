@@ -0,0 +1,16 @@
+#![allow(unused_imports)]
+
+use relm::Widget;
+use relm_derive::widget;
+
+#[widget]
+impl Widget for Foo {
+    fn model() {}
+
+    fn update(&mut self, _: ()) {}
+
+    // The path is relative to the crate generated by trybuild in target/tests/trybuild.
+    view!("../../../../tests/ui/syntax_error_in_view_file.relm");
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+#![allow(unused_imports)]
+
+use relm::Widget;
+use relm_derive::widget;
+
+pub struct Model {
+    visible: bool,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            visible: true,
+        }
+    }
+
+    fn update(&mut self, _: ()) {}
+
+    view! {
+        gtk::Box {
+            #[name="check_button"]
+            gtk::CheckButton {
+            },
+            if self.model.visible {
+                gtk::Button {
+                    sensitive: bind check_button.active,
+                }
+            }
+            else {
+                gtk::Label {
+                }
+            },
+        }
+    }
+}
+
+fn main() {}
@@ -21,21 +21,16 @@
 
 use gtk::{
     Inhibit,
-    prelude::GtkMenuItemExt,
     prelude::MenuShellExt,
     prelude::OrientableExt,
     prelude::WidgetExt,
 };
 use gtk::Orientation::Vertical;
-use relm::{Relm, Widget, connect};
+use relm::Widget;
 use relm_derive::{Msg, widget};
 
 use self::Msg::*;
 
-pub struct Model {
-    relm: Relm<Win>,
-}
-
 #[derive(Msg)]
 pub enum Msg {
     Quit,
@@ -44,21 +39,10 @@ pub enum Msg {
 #[widget]
 impl Widget for Win {
     fn init_view(&mut self) {
-        let file_menu = gtk::Menu::new();
-        let file_item = gtk::MenuItem::with_label("File");
-        file_item.set_submenu(Some(&file_menu));
-        let quit_item = gtk::MenuItem::with_label("Quit");
-        self.widgets.menubar.append(&file_item);
-        file_menu.append(&quit_item);
-        self.widgets.menubar.show_all();
-
-        connect!(quit_item, connect_activate(_), self.model.relm, Quit);
+        self.widgets.menubar.bind_model(Some(&self.main_menu), None, true);
     }
 
-    fn model(relm: &Relm<Self>, _: ()) -> Model {
-        Model {
-            relm: relm.clone(),
-        }
+    fn model() -> () {
     }
 
     fn update(&mut self, event: Msg) {
@@ -67,6 +51,14 @@ impl Widget for Win {
         }
     }
 
+    menu! {
+        main_menu {
+            submenu "File" {
+                "Quit" => Quit,
+            },
+        }
+    }
+
     view! {
         gtk::Window {
             gtk::Box {
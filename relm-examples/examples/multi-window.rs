@@ -20,59 +20,90 @@
  */
 
 use gtk::{
-    Inhibit,
-    prelude::WidgetExt,
+    prelude::ButtonExt,
+    prelude::GtkWindowExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
 };
-use relm::{Component, Widget, init};
+use gtk::Orientation::Vertical;
+use relm::{QuitPolicy, Relm, Widget, WindowEvent, WindowManager};
 use relm_derive::{Msg, widget};
 
 use self::Msg::*;
 
+#[derive(Msg)]
+pub enum SecondaryMsg {
+}
+
 #[widget]
 impl Widget for SecondaryWin {
     fn model() {
     }
 
-    fn update(&mut self, _msg: Msg) {
+    fn update(&mut self, _msg: SecondaryMsg) {
     }
 
     view! {
         gtk::Window {
-            delete_event(_, _) => (Quit, Inhibit(false)),
+            title: "Secondary window",
         }
     }
 }
 
 pub struct Model {
-    _win: Component<SecondaryWin>,
+    open_windows: usize,
+    windows: WindowManager<Msg>,
 }
 
 #[derive(Msg)]
 pub enum Msg {
-    Quit,
+    Open,
+    Windows(WindowEvent),
 }
 
 #[widget]
 impl Widget for Win {
-    fn model() -> Model {
+    fn init_view(&mut self) {
+        let _ = self.model.windows.add(&self.root());
+    }
+
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
         Model {
-            _win: init::<SecondaryWin>(()).expect("secondary window"),
+            open_windows: 0,
+            // Quit when the last window is closed.
+            windows: WindowManager::new(relm.stream().clone(), QuitPolicy::LastWindowClosed, Windows),
         }
     }
 
     fn update(&mut self, event: Msg) {
         match event {
-            Quit => gtk::main_quit(),
+            Open => {
+                if let Err(error) = self.model.windows.open::<SecondaryWin>(()) {
+                    eprintln!("Cannot open a window: {}", error);
+                }
+            },
+            Windows(WindowEvent::Opened(_)) => self.model.open_windows += 1,
+            Windows(WindowEvent::Closed(_)) => self.model.open_windows -= 1,
         }
     }
 
     view! {
         gtk::Window {
-            delete_event(_, _) => (Quit, Inhibit(false)),
+            title: "Main window",
+            gtk::Box {
+                orientation: Vertical,
+                gtk::Button {
+                    clicked => Open,
+                    label: "Open a window",
+                },
+                gtk::Label {
+                    text: &self.model.open_windows.to_string(),
+                },
+            },
         }
     }
 }
 
 fn main() {
-    Win::run(()).unwrap();
+    Win::run(()).expect("Win::run failed");
 }
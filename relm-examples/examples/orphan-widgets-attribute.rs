@@ -63,7 +63,7 @@ impl Widget for Win {
             End => println!("End"),
             Increment => self.model.counter += 1,
             Move(x, y) => println!("Moved to {}, {}", x, y),
-            Quit => gtk::main_quit(),
+            Quit => relm::quit(()),
         }
     }
 
@@ -0,0 +1,159 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::CounterMsg::*;
+use self::Msg::*;
+
+pub struct CounterModel {
+    counter: i32,
+}
+
+#[derive(Msg)]
+pub enum CounterMsg {
+    Increment,
+}
+
+#[widget]
+impl Widget for Counter {
+    fn model() -> CounterModel {
+        CounterModel {
+            counter: 0,
+        }
+    }
+
+    fn update(&mut self, event: CounterMsg) {
+        match event {
+            Increment => self.model.counter += 1,
+        }
+    }
+
+    // The accelerators of a child component are added to the window containing it.
+    accelerators! {
+        /// Increment the counter
+        "<Control>i" => Increment,
+    }
+
+    view! {
+        #[name="label"]
+        gtk::Label {
+            text: &self.model.counter.to_string(),
+        }
+    }
+}
+
+pub struct Model {
+    help: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Help,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            help: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Help => {
+                self.model.help = self.accelerators.shortcuts().iter()
+                    .map(|shortcut| format!("{}: {}", shortcut.label(), shortcut.description().unwrap_or_default()))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+            },
+            Quit => relm::quit(()),
+        }
+    }
+
+    accelerators! {
+        /// Show the keyboard shortcuts
+        "F1" => Help,
+        /// Quit
+        "<Control>q" => Quit,
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="counter"]
+                Counter,
+                #[name="help_label"]
+                gtk::Label {
+                    text: &self.model.help,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::prelude::LabelExt;
+
+    use gtk_test::{assert_text, find_child_by_name, wait};
+
+    use crate::Win;
+
+    fn press(window: &gtk::Window, accelerator: &str) {
+        let (key, modifiers) = gtk::accelerator_parse(accelerator);
+        assert!(gtk::accel_groups_activate(window, key, modifiers));
+        wait(10);
+    }
+
+    #[test]
+    fn accelerators() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let window = component.widget();
+        let counter_label: gtk::Label = find_child_by_name(&widgets.counter, "label").expect("counter label");
+        let help_label = &widgets.help_label;
+
+        press(window, "<Control>i");
+        assert_text!(counter_label, 1);
+
+        press(window, "F1");
+        let help = help_label.text();
+        assert!(help.contains(": Show the keyboard shortcuts"));
+        assert!(help.contains(": Quit"));
+        assert!(!help.contains("Increment"));
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::ActionableExt,
+    prelude::ButtonExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    counter: i32,
+    dark_theme: bool,
+    mode: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Add(i32),
+    DarkTheme(bool),
+    Increment,
+    Mode(String),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            counter: 0,
+            dark_theme: false,
+            mode: "normal".to_string(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Add(value) => self.model.counter += value,
+            DarkTheme(dark_theme) => self.model.dark_theme = dark_theme,
+            Increment => self.model.counter += 1,
+            Mode(mode) => self.model.mode = mode,
+            Quit => relm::quit(()),
+        }
+    }
+
+    // The actions are installed on the window as the "win" action group.
+    actions! {
+        prefix: "win",
+        add(i32) => Add,
+        // The state of the actions is updated when the model changes.
+        dark_theme(bool) = self.model.dark_theme => DarkTheme,
+        increment => Increment,
+        mode(String) = self.model.mode => Mode,
+        quit => Quit,
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="inc_button"]
+                gtk::Button {
+                    action_name: Some("win.increment"),
+                    label: "+",
+                },
+                #[name="dark_check"]
+                gtk::CheckButton {
+                    action_name: Some("win.dark_theme"),
+                    label: "Dark theme",
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.counter.to_string(),
+                },
+                #[name="mode_label"]
+                gtk::Label {
+                    text: &self.model.mode,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gio::prelude::ActionGroupExt;
+    use glib::ToVariant;
+    use gtk::prelude::{LabelExt, ToggleButtonExt, WidgetExt};
+
+    use gtk_test::{assert_text, wait};
+    use relm_test::click;
+
+    use crate::Win;
+
+    #[test]
+    fn actions() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let group = component.widget().action_group("win").expect("action group");
+        let label = &widgets.label;
+        let mode_label = &widgets.mode_label;
+
+        click(&widgets.inc_button);
+        assert_text!(label, 1);
+
+        group.activate_action("add", Some(&5.to_variant()));
+        wait(10);
+        assert_text!(label, 6);
+
+        click(&widgets.dark_check);
+        assert!(widgets.dark_check.is_active());
+        assert_eq!(group.action_state("dark_theme"), Some(true.to_variant()));
+
+        group.activate_action("mode", Some(&"compact".to_variant()));
+        wait(10);
+        assert_text!(mode_label, "compact");
+        assert_eq!(group.action_state("mode"), Some("compact".to_variant()));
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gio::prelude::FileExt;
+use gtk::prelude::LabelExt;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    files: Vec<String>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Open(Vec<gio::File>),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            files: vec![],
+        }
+    }
+
+    // Send the files given on the command line to update().
+    fn open(files: Vec<gio::File>, _hint: String) -> Option<Msg> {
+        Some(Open(files))
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Open(files) => {
+                self.model.files = files.iter()
+                    .filter_map(|file| file.basename())
+                    .map(|name| name.display().to_string())
+                    .collect();
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.files.join(", "),
+            },
+        }
+    }
+}
+
+// The application quits when the window is closed.
+fn main() {
+    let status = relm::run_application::<Win>("org.relm.ApplicationAttribute", gio::ApplicationFlags::HANDLES_OPEN, ());
+    std::process::exit(status);
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::prelude::LabelExt;
+
+    use gtk_test::{assert_text, wait};
+
+    use crate::Win;
+
+    #[test]
+    fn open_files() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let label = &widgets.label;
+
+        assert_text!(label, "");
+
+        let files = vec![gio::File::for_path("first.txt"), gio::File::for_path("second.txt")];
+        let msg = <Win as relm::Widget>::open(files, String::new()).expect("open message");
+        component.emit(msg);
+        wait(10);
+        assert_text!(label, "first.txt, second.txt");
+    }
+}
@@ -0,0 +1,134 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gio::prelude::{ApplicationCommandLineExt, ApplicationExt, FileExt};
+use gtk::prelude::{Cast, GtkApplicationExt, LabelExt};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+pub struct Model {
+    log: Log,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    CommandLine(Vec<String>),
+    Open(Vec<gio::File>),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(log: Log) -> Model {
+        log.borrow_mut().push("model".to_string());
+        Model {
+            log,
+        }
+    }
+
+    fn open(files: Vec<gio::File>, _hint: String) -> Option<Msg> {
+        Some(Open(files))
+    }
+
+    // The exit status is set before the message is sent to update().
+    fn command_line(command_line: gio::ApplicationCommandLine) -> Option<Msg> {
+        let args: Vec<String> = command_line.arguments().iter()
+            .skip(1)
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        if args.iter().any(|arg| arg == "--fail") {
+            command_line.set_exit_status(3);
+        }
+        Some(CommandLine(args))
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            CommandLine(args) => self.model.log.borrow_mut().push(format!("command line: {}", args.join(" "))),
+            Open(files) => {
+                let names: Vec<String> = files.iter()
+                    .filter_map(|file| file.basename())
+                    .map(|name| name.display().to_string())
+                    .collect();
+                self.model.log.borrow_mut().push(format!("open: {}", names.join(", ")));
+                // The existing window is presented instead of creating a new widget.
+                let application = gio::Application::default().expect("default application");
+                application.activate();
+                let windows = application.downcast_ref::<gtk::Application>().expect("gtk application").windows();
+                self.model.log.borrow_mut().push(format!("windows: {}", windows.len()));
+            },
+        }
+        // Quit the application, since relm::run() is not used.
+        relm::quit(());
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Label {
+                text: "Application",
+            },
+        }
+    }
+}
+
+fn main() {
+    let status = relm::run_application::<Win>("org.relm.ApplicationSignalsAttribute",
+        gio::ApplicationFlags::HANDLES_COMMAND_LINE, Log::default());
+    std::process::exit(status);
+}
+
+#[cfg(test)]
+mod tests {
+    use gio::ApplicationFlags;
+
+    use crate::{Log, Win};
+
+    // The applications are run one after the other since GTK can only be used from one thread.
+    #[test]
+    fn application_signals() {
+        let log = Log::default();
+        let status = relm::run_application_with_args::<Win, _>("org.relm.ApplicationSignalsOpen",
+            ApplicationFlags::HANDLES_OPEN | ApplicationFlags::NON_UNIQUE, log.clone(),
+            &["application-signals", "first.txt", "second.txt"]);
+        assert_eq!(status, 0);
+        assert_eq!(*log.borrow(), vec!["model", "open: first.txt, second.txt", "windows: 1"]);
+
+        let log = Log::default();
+        let status = relm::run_application_with_args::<Win, _>("org.relm.ApplicationSignalsCommandLine",
+            ApplicationFlags::HANDLES_COMMAND_LINE | ApplicationFlags::NON_UNIQUE, log.clone(),
+            &["application-signals", "--verbose"]);
+        assert_eq!(status, 0);
+        assert_eq!(*log.borrow(), vec!["model", "command line: --verbose"]);
+
+        let log = Log::default();
+        let status = relm::run_application_with_args::<Win, _>("org.relm.ApplicationSignalsExitStatus",
+            ApplicationFlags::HANDLES_COMMAND_LINE | ApplicationFlags::NON_UNIQUE, log.clone(),
+            &["application-signals", "--fail"]);
+        assert_eq!(status, 3);
+        assert_eq!(*log.borrow(), vec!["model", "command line: --fail"]);
+    }
+}
@@ -0,0 +1,142 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk::{
+    Inhibit,
+    prelude::EntryExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    counter: i32,
+    renders: Rc<Cell<u32>>,
+    text: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    AddFive,
+    Quit,
+    SetText(String),
+}
+
+fn render(counter: i32, renders: &Cell<u32>) -> String {
+    renders.set(renders.get() + 1);
+    counter.to_string()
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(renders: Rc<Cell<u32>>) -> Model {
+        Model {
+            counter: 0,
+            renders,
+            text: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            AddFive => {
+                for _ in 0..5 {
+                    self.model.counter += 1;
+                }
+            },
+            Quit => gtk::main_quit(),
+            SetText(text) => self.model.text = text,
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="label"]
+                gtk::Label {
+                    text: &render(self.model.counter, &self.model.renders),
+                },
+                #[name="entry"]
+                gtk::Entry {
+                    text: &self.model.text,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(Rc::new(Cell::new(0))).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use gtk::prelude::{EntryExt, LabelExt};
+
+    use gtk_test::{assert_text, wait};
+
+    use crate::Msg::{AddFive, SetText};
+    use crate::Win;
+
+    #[test]
+    fn batch_updates() {
+        let renders = Rc::new(Cell::new(0));
+        let (component, _, widgets) = relm::init_test::<Win>(renders.clone()).expect("init_test failed");
+        let label = &widgets.label;
+        assert_eq!(renders.get(), 1);
+
+        component.emit(AddFive);
+        wait(10);
+        assert_text!(label, 5);
+        // The label is only updated once, after update() returns.
+        assert_eq!(renders.get(), 2);
+    }
+
+    #[test]
+    fn value_changed_by_the_user() {
+        let (component, _, widgets) = relm::init_test::<Win>(Rc::new(Cell::new(0))).expect("init_test failed");
+        let entry = &widgets.entry;
+
+        component.emit(SetText("Celsius".to_string()));
+        wait(10);
+        assert_text!(entry, "Celsius");
+
+        // The text typed by the user is replaced even though the model did not change since the
+        // last time the property was set.
+        entry.set_text("typed");
+        component.emit(SetText("Celsius".to_string()));
+        wait(10);
+        assert_text!(entry, "Celsius");
+    }
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::EntryExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::ToggleButtonExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    name: String,
+    visible: bool,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    NestedLoop,
+    Quit,
+    Reset,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            name: String::new(),
+            visible: true,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            // The entry is changed while the component is being updated, like in a dialog.
+            NestedLoop => {
+                let main_loop = glib::MainLoop::new(None, false);
+                let entry = self.widgets.entry.clone();
+                let nested_loop = main_loop.clone();
+                glib::idle_add_local_once(move || {
+                    entry.set_text("Bob");
+                    nested_loop.quit();
+                });
+                main_loop.run();
+            },
+            Quit => gtk::main_quit(),
+            Reset => {
+                self.model.name = "Nobody".to_string();
+                self.model.visible = true;
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="entry"]
+                gtk::Entry {
+                    text: bind self.model.name,
+                },
+                #[name="check_button"]
+                gtk::CheckButton {
+                    active: bind self.model.visible,
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.name,
+                    visible: self.model.visible,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::prelude::{EntryExt, LabelExt, ToggleButtonExt, WidgetExt};
+
+    use gtk_test::{assert_text, enter_keys, wait};
+
+    use crate::Msg::{NestedLoop, Reset};
+    use crate::Win;
+
+    #[test]
+    fn two_way_binding() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let entry = &widgets.entry;
+        let check_button = &widgets.check_button;
+        let label = &widgets.label;
+
+        enter_keys(entry, "Alice");
+        wait(10);
+        assert_text!(label, "Alice");
+
+        check_button.set_active(false);
+        wait(10);
+        assert!(!label.is_visible());
+
+        component.emit(Reset);
+        wait(10);
+        assert_text!(entry, "Nobody");
+        assert_text!(label, "Nobody");
+        assert!(check_button.is_active());
+        assert!(label.is_visible());
+
+        // The change is applied once update() returns.
+        component.emit(NestedLoop);
+        wait(10);
+        assert_text!(label, "Bob");
+    }
+}
@@ -0,0 +1,220 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::ButtonExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::CounterMsg::*;
+use self::Msg::*;
+
+pub struct CounterModel {
+    counter: i32,
+}
+
+#[derive(Msg)]
+pub enum CounterMsg {
+    Increment,
+    Reset(i32),
+}
+
+#[widget]
+impl Widget for Counter {
+    fn model() -> CounterModel {
+        CounterModel {
+            counter: 0,
+        }
+    }
+
+    fn update(&mut self, event: CounterMsg) {
+        match event {
+            Increment => self.model.counter += 1,
+            Reset(value) => self.model.counter = value,
+        }
+    }
+
+    view! {
+        gtk::Box {
+            orientation: Vertical,
+            gtk::Button {
+                label: "+",
+                widget_name: "inc_button",
+                clicked => Increment,
+            },
+            gtk::Label {
+                widget_name: "label",
+                text: &self.model.counter.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Status {
+    Connected,
+    Connecting,
+    Disconnected,
+}
+
+pub struct Model {
+    initial: i32,
+    logged_in: bool,
+    status: Status,
+    user: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Initial(i32),
+    Quit,
+    SetStatus(Status),
+    ToggleLogin,
+    User(String),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            initial: 0,
+            logged_in: false,
+            status: Status::Disconnected,
+            user: "nobody".to_string(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Initial(value) => self.model.initial = value,
+            Quit => gtk::main_quit(),
+            SetStatus(status) => self.model.status = status,
+            ToggleLogin => self.model.logged_in = !self.model.logged_in,
+            User(user) => self.model.user = user,
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="vbox"]
+            gtk::Box {
+                orientation: Vertical,
+                #[name="login_button"]
+                gtk::Button {
+                    clicked => ToggleLogin,
+                    label: "Toggle",
+                },
+                if self.model.logged_in {
+                    gtk::Label {
+                        widget_name: "user_label",
+                        text: &self.model.user,
+                    }
+                }
+                else {
+                    gtk::Box {
+                        widget_name: "counter_box",
+                        Counter {
+                            Reset: self.model.initial,
+                        },
+                    }
+                },
+                match self.model.status {
+                    Status::Connected => gtk::Label {
+                        widget_name: "status_label",
+                        text: "Connected",
+                    },
+                    Status::Connecting | Status::Disconnected if self.model.logged_in => gtk::Spinner {
+                        widget_name: "spinner",
+                    },
+                    Status::Connecting | Status::Disconnected => gtk::Button {
+                        widget_name: "connect_button",
+                        clicked => SetStatus(Status::Connected),
+                        label: "Connect",
+                    },
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{Button, Label, Spinner, prelude::LabelExt};
+
+    use gtk_test::{assert_text, find_child_by_name, wait};
+    use relm_test::click;
+
+    use crate::Msg::{Initial, User};
+    use crate::Win;
+
+    #[test]
+    fn conditions() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let vbox = &widgets.vbox;
+        let login_button = &widgets.login_button;
+
+        assert!(find_child_by_name::<Label, _>(vbox, "user_label").is_none());
+        let label: Label = find_child_by_name(vbox, "label").expect("counter label");
+        assert_text!(label, 0);
+        let inc_button: Button = find_child_by_name(vbox, "inc_button").expect("inc button");
+        click(&inc_button);
+        assert_text!(label, 1);
+        component.emit(Initial(5));
+        wait(10);
+        assert_text!(label, 5);
+
+        assert!(find_child_by_name::<Spinner, _>(vbox, "spinner").is_none());
+        assert!(find_child_by_name::<Button, _>(vbox, "connect_button").is_some());
+
+        click(login_button);
+        assert!(find_child_by_name::<Label, _>(vbox, "label").is_none());
+        let user_label: Label = find_child_by_name(vbox, "user_label").expect("user label");
+        assert_text!(user_label, "nobody");
+        component.emit(User("antoyo".to_string()));
+        wait(10);
+        assert_text!(user_label, "antoyo");
+
+        assert!(find_child_by_name::<Spinner, _>(vbox, "spinner").is_some());
+        assert!(find_child_by_name::<Button, _>(vbox, "connect_button").is_none());
+
+        click(login_button);
+        assert!(find_child_by_name::<Label, _>(vbox, "user_label").is_none());
+        let label: Label = find_child_by_name(vbox, "label").expect("counter label");
+        assert_text!(label, 5);
+
+        let connect_button: Button = find_child_by_name(vbox, "connect_button").expect("connect button");
+        click(&connect_button);
+        assert!(find_child_by_name::<Button, _>(vbox, "connect_button").is_none());
+        let status_label: Label = find_child_by_name(vbox, "status_label").expect("status label");
+        assert_text!(status_label, "Connected");
+    }
+}
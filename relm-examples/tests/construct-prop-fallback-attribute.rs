@@ -0,0 +1,206 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+// A custom widget, which has no builder.
+mod custom {
+    mod imp {
+        use gtk::subclass::prelude::{BoxImpl, ContainerImpl, ObjectImpl, ObjectSubclass, WidgetImpl};
+
+        #[derive(Default)]
+        pub struct ButtonBox;
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for ButtonBox {
+            const NAME: &'static str = "RelmTestButtonBox";
+            type Type = super::ButtonBox;
+            type ParentType = gtk::Box;
+        }
+
+        impl ObjectImpl for ButtonBox {}
+        impl WidgetImpl for ButtonBox {}
+        impl ContainerImpl for ButtonBox {}
+        impl BoxImpl for ButtonBox {}
+    }
+
+    glib::wrapper! {
+        pub struct ButtonBox(ObjectSubclass<imp::ButtonBox>)
+            @extends gtk::Box, gtk::Container, gtk::Widget,
+            @implements gtk::Orientable;
+    }
+
+    mod counter_imp {
+        use std::cell::Cell;
+        use std::sync::OnceLock;
+
+        use glib::{ParamSpec, ParamSpecBuilderExt, ParamSpecInt, ToValue, Value};
+        use gtk::subclass::prelude::{BoxImpl, ContainerImpl, ObjectImpl, ObjectSubclass, WidgetImpl};
+
+        #[derive(Default)]
+        pub struct Counter {
+            pub start: Cell<i32>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for Counter {
+            const NAME: &'static str = "RelmTestCounter";
+            type Type = super::Counter;
+            type ParentType = gtk::Box;
+        }
+
+        impl ObjectImpl for Counter {
+            fn properties() -> &'static [ParamSpec] {
+                static PROPERTIES: OnceLock<Vec<ParamSpec>> = OnceLock::new();
+                PROPERTIES.get_or_init(|| vec![ParamSpecInt::builder("start").construct_only().build()])
+            }
+
+            fn set_property(&self, _id: usize, value: &Value, _pspec: &ParamSpec) {
+                self.start.set(value.get().expect("start"));
+            }
+
+            fn property(&self, _id: usize, _pspec: &ParamSpec) -> Value {
+                self.start.get().to_value()
+            }
+        }
+
+        impl WidgetImpl for Counter {}
+        impl ContainerImpl for Counter {}
+        impl BoxImpl for Counter {}
+    }
+
+    glib::wrapper! {
+        pub struct Counter(ObjectSubclass<counter_imp::Counter>)
+            @extends gtk::Box, gtk::Container, gtk::Widget,
+            @implements gtk::Orientable;
+    }
+
+    impl Counter {
+        pub fn builder() -> CounterBuilder {
+            CounterBuilder {
+                builder: glib::Object::builder(),
+            }
+        }
+
+        pub fn start(&self) -> i32 {
+            glib::ObjectExt::property(self, "start")
+        }
+    }
+
+    // A typed builder without a setter for the construct-only property `start`.
+    pub struct CounterBuilder {
+        builder: glib::object::ObjectBuilder<'static, Counter>,
+    }
+
+    impl CounterBuilder {
+        pub fn spacing(self, spacing: i32) -> Self {
+            CounterBuilder {
+                builder: self.builder.property("spacing", spacing),
+            }
+        }
+
+        pub fn build(self) -> Counter {
+            self.builder.build()
+        }
+    }
+}
+
+pub struct Model {
+    spacing: i32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            spacing: 6,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => relm::quit(()),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="button_box"]
+            custom::ButtonBox({ orientation: Vertical, spacing: self.model.spacing }) {
+                #[name="radio1"]
+                gtk::RadioButton({ label: "First" }) {
+                },
+                // The builder of gtk::RadioButton has no setter for the group.
+                #[name="radio2"]
+                gtk::RadioButton({ group: self.radio1, label: "Second" }) {
+                },
+                // The construct-only property is given when the widget is constructed.
+                #[name="counter"]
+                custom::Counter({ spacing: 4, start: self.model.spacing }) {
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::Orientation::Vertical;
+    use gtk::prelude::{BoxExt, ButtonExt, OrientableExt, RadioButtonExt};
+
+    use crate::Win;
+
+    #[test]
+    fn construct_properties_without_setter() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let button_box = &widgets.button_box;
+        let radio1 = &widgets.radio1;
+        let radio2 = &widgets.radio2;
+
+        assert_eq!(button_box.orientation(), Vertical);
+        assert_eq!(button_box.spacing(), 6);
+
+        assert_eq!(radio2.label().as_deref(), Some("Second"));
+        assert!(radio2.group().contains(radio1));
+        assert_eq!(radio1.group().len(), 2);
+
+        assert_eq!(widgets.counter.start(), 6);
+        assert_eq!(widgets.counter.spacing(), 4);
+    }
+}
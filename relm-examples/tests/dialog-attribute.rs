@@ -0,0 +1,229 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::ButtonExt,
+    prelude::EntryExt,
+    prelude::GtkWindowExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{Dialog, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+use self::NameMsg::*;
+
+pub enum NameOutput {
+    Accepted(String),
+    Cancelled,
+}
+
+pub struct NameModel {
+    name: String,
+    relm: Relm<NameDialog>,
+}
+
+#[derive(Msg)]
+pub enum NameMsg {
+    Accept,
+    Cancel,
+    Done(Option<String>),
+}
+
+#[widget]
+impl Widget for NameDialog {
+    fn model(relm: &Relm<Self>, name: String) -> NameModel {
+        NameModel {
+            name,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: NameMsg) {
+        match event {
+            Accept => self.model.relm.stream().emit(Done(Some(self.model.name.clone()))),
+            Cancel => self.model.relm.stream().emit(Done(None)),
+            Done(_) => (),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            title: "Edit the name",
+            gtk::Box {
+                orientation: Vertical,
+                #[name="entry"]
+                gtk::Entry {
+                    text: bind self.model.name,
+                },
+                #[name="ok_button"]
+                gtk::Button {
+                    clicked => Accept,
+                    label: "OK",
+                },
+                #[name="cancel_button"]
+                gtk::Button {
+                    clicked => Cancel,
+                    label: "Cancel",
+                },
+            },
+        }
+    }
+}
+
+impl Dialog for NameDialog {
+    type Output = NameOutput;
+
+    fn output(msg: &NameMsg) -> Option<NameOutput> {
+        match *msg {
+            Done(Some(ref name)) => Some(NameOutput::Accepted(name.clone())),
+            Done(None) => Some(NameOutput::Cancelled),
+            _ => None,
+        }
+    }
+
+    fn cancelled() -> NameOutput {
+        NameOutput::Cancelled
+    }
+}
+
+pub struct Model {
+    name: String,
+    relm: Relm<Win>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Edit,
+    Edited(NameOutput),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            name: "Alice".to_string(),
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Edit => {
+                match relm::dialog::<NameDialog>(&self.root(), self.model.name.clone()) {
+                    Ok(response) => response.connect(self.model.relm.stream(), Edited),
+                    Err(error) => eprintln!("Cannot open the dialog: {}", error),
+                }
+            },
+            Edited(NameOutput::Accepted(name)) => self.model.name = name,
+            Edited(NameOutput::Cancelled) => (),
+            Quit => relm::quit(()),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="edit_button"]
+                gtk::Button {
+                    clicked => Edit,
+                    label: "Edit",
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.name,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{Button, Entry, Window};
+    use gtk::prelude::{Cast, EntryExt, GtkWindowExt, LabelExt};
+
+    use gtk_test::{assert_text, click, find_child_by_name, wait};
+
+    use crate::Win;
+
+    fn find_dialog() -> Option<Window> {
+        Window::list_toplevels().into_iter()
+            .filter_map(|widget| widget.downcast::<Window>().ok())
+            .find(|window| window.title().map(|title| title == "Edit the name").unwrap_or(false))
+    }
+
+    #[test]
+    fn dialog() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let edit_button = &widgets.edit_button;
+        let label = &widgets.label;
+        let window = component.widget();
+
+        assert!(find_dialog().is_none());
+
+        click(edit_button);
+        wait(10);
+        let dialog = find_dialog().expect("dialog");
+        assert!(dialog.is_modal());
+        assert_eq!(dialog.transient_for().as_ref(), Some(window));
+
+        let entry: Entry = find_child_by_name(&dialog, "entry").expect("entry");
+        assert_text!(entry, "Alice");
+        entry.set_text("Bob");
+        let ok_button: Button = find_child_by_name(&dialog, "ok_button").expect("ok button");
+        click(&ok_button);
+        wait(10);
+        assert_text!(label, "Bob");
+        assert!(find_dialog().is_none());
+
+        // Closing the dialog cancels it.
+        click(edit_button);
+        wait(10);
+        let dialog = find_dialog().expect("dialog");
+        let entry: Entry = find_child_by_name(&dialog, "entry").expect("entry");
+        entry.set_text("Carol");
+        dialog.close();
+        wait(10);
+        assert_text!(label, "Bob");
+        assert!(find_dialog().is_none());
+
+        click(edit_button);
+        wait(10);
+        let dialog = find_dialog().expect("dialog");
+        let cancel_button: Button = find_child_by_name(&dialog, "cancel_button").expect("cancel button");
+        click(&cancel_button);
+        wait(10);
+        assert_text!(label, "Bob");
+        assert!(find_dialog().is_none());
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::ButtonExt,
+    prelude::GtkWindowExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::ConfirmMsg::*;
+use self::Msg::*;
+
+#[derive(Debug, PartialEq)]
+pub struct ExitCode(i32);
+
+#[derive(Msg)]
+pub enum ConfirmMsg {
+    Answer(bool),
+}
+
+// Run in a nested main loop: its answer is the value given to relm::quit().
+#[widget]
+impl Widget for Confirm {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: ConfirmMsg) {
+        match event {
+            Answer(answer) => relm::quit(answer),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            title: "Increment?",
+            gtk::Box {
+                gtk::Button {
+                    clicked => Answer(true),
+                    label: "Yes",
+                },
+                gtk::Button {
+                    clicked => Answer(false),
+                    label: "No",
+                },
+            },
+            delete_event(_, _) => (Answer(false), Inhibit(false)),
+        }
+    }
+}
+
+pub struct Model {
+    counter: i32,
+    relm: Relm<Win>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Ask,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            counter: 0,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Ask => {
+                let mut exit = Confirm::run(()).expect("Confirm::run failed");
+                if exit.value() == Some(true) {
+                    self.model.counter += 1;
+                }
+            },
+            Quit => self.model.relm.quit(ExitCode(self.model.counter)),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                gtk::Button {
+                    clicked => Ask,
+                    label: "Ask",
+                },
+                gtk::Label {
+                    text: &self.model.counter.to_string(),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    let mut exit = Win::run(()).expect("Win::run failed");
+    if let Some(model) = exit.model.take() {
+        println!("Incremented {} times", model.counter);
+    }
+    let ExitCode(code) = exit.value().unwrap_or(ExitCode(1));
+    std::process::exit(code);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Confirm, ExitCode, Win};
+
+    #[test]
+    fn exit_status() {
+        glib::idle_add_local_once(|| {
+            // Only the innermost loop quits.
+            glib::idle_add_local_once(|| relm::quit(true));
+            let mut exit = relm::run::<Confirm>(()).expect("run failed");
+            assert_eq!(exit.value::<ExitCode>(), None);
+            assert_eq!(exit.value::<bool>(), Some(true));
+            relm::quit(ExitCode(3));
+        });
+
+        let mut exit = relm::run::<Win>(()).expect("run failed");
+        assert_eq!(exit.value::<ExitCode>(), Some(ExitCode(3)));
+        assert_eq!(exit.model.expect("final model").counter, 0);
+
+        // gtk::main_quit() also quits the loop, without a value.
+        glib::idle_add_local_once(gtk::main_quit);
+        let mut exit = relm::run::<Win>(()).expect("run failed");
+        assert_eq!(exit.value::<ExitCode>(), None);
+    }
+}
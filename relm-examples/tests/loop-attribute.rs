@@ -0,0 +1,161 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::ButtonExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Clone)]
+pub struct Item {
+    id: u32,
+    name: String,
+}
+
+pub struct Model {
+    items: Vec<Item>,
+    next_id: u32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Add(String),
+    Quit,
+    Remove(u32),
+    Rename(usize, String),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            items: vec![
+                Item { id: 0, name: "first".to_string() },
+                Item { id: 1, name: "second".to_string() },
+            ],
+            next_id: 2,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Add(name) => {
+                let mut items = self.model.items.clone();
+                items.push(Item { id: self.model.next_id, name });
+                self.model.next_id += 1;
+                self.model.items = items;
+            },
+            Quit => gtk::main_quit(),
+            Remove(id) => {
+                let mut items = self.model.items.clone();
+                items.retain(|item| item.id != id);
+                self.model.items = items;
+            },
+            Rename(index, name) => {
+                let mut items = self.model.items.clone();
+                items[index].name = name;
+                self.model.items = items;
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="vbox"]
+            gtk::Box {
+                orientation: Vertical,
+                for &Item { id, ref name } in &self.model.items; key = id {
+                    gtk::Button {
+                        clicked => Remove(id),
+                        label: name,
+                    }
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{Box, Button, prelude::ButtonExt, prelude::Cast, prelude::ContainerExt};
+
+    use gtk_test::{assert_label, wait};
+    use relm_test::click;
+
+    use crate::Msg::{Add, Rename};
+    use crate::Win;
+
+    fn buttons(vbox: &Box) -> Vec<Button> {
+        let list = vbox.children()[0].clone().downcast::<Box>().expect("loop box");
+        list.children().into_iter()
+            .map(|child| child.downcast::<Button>().expect("button"))
+            .collect()
+    }
+
+    #[test]
+    fn loops() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let vbox = &widgets.vbox;
+
+        let items = buttons(vbox);
+        assert_eq!(items.len(), 2);
+        assert_label!(items[0], "first");
+        assert_label!(items[1], "second");
+
+        component.emit(Add("third".to_string()));
+        wait(10);
+        let items = buttons(vbox);
+        assert_eq!(items.len(), 3);
+        assert_label!(items[2], "third");
+
+        // The widgets are keyed by the id of the items: the button of the removed item is destroyed
+        // and the buttons of the next items are kept, with their events.
+        click(&items[1]);
+        let new_items = buttons(vbox);
+        assert_eq!(new_items.len(), 2);
+        assert_eq!(new_items[0], items[0]);
+        assert_eq!(new_items[1], items[2]);
+        assert_label!(new_items[0], "first");
+        assert_label!(new_items[1], "third");
+
+        component.emit(Rename(1, "last".to_string()));
+        wait(10);
+        let items = buttons(vbox);
+        assert_label!(items[1], "last");
+
+        click(&items[1]);
+        let items = buttons(vbox);
+        assert_eq!(items.len(), 1);
+        assert_label!(items[0], "first");
+    }
+}
@@ -0,0 +1,143 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::LabelExt,
+    prelude::MenuButtonExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    counter: i32,
+    dark_theme: bool,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    DarkTheme(bool),
+    Increment,
+    Quit,
+    Reset,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            counter: 0,
+            dark_theme: false,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            DarkTheme(dark_theme) => self.model.dark_theme = dark_theme,
+            Increment => self.model.counter += 1,
+            Quit => relm::quit(()),
+            Reset => self.model.counter = 0,
+        }
+    }
+
+    actions! {
+        dark_theme(bool) = self.model.dark_theme => DarkTheme,
+        quit => Quit,
+    }
+
+    // The menu is saved in the main_menu attribute.
+    menu! {
+        main_menu {
+            // An action is created for the items emitting a message.
+            "Increment" => Increment,
+            // The item is only sensitive when the condition is true.
+            "Reset" => Reset if self.model.counter != 0,
+            section {
+                "Dark theme" => "win.dark_theme",
+            },
+            submenu "File" {
+                "Quit" => "win.quit",
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="menu_button"]
+                gtk::MenuButton {
+                    menu_model: Some(&self.main_menu),
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.counter.to_string(),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gio::prelude::{ActionGroupExt, MenuModelExt};
+    use glib::ToVariant;
+    use gtk::prelude::{LabelExt, MenuButtonExt, WidgetExt};
+
+    use gtk_test::{assert_text, wait};
+
+    use crate::Win;
+
+    #[test]
+    fn menu() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let group = component.widget().action_group("win").expect("action group");
+        let menu = widgets.menu_button.menu_model().expect("menu model");
+        let label = &widgets.label;
+
+        assert_eq!(menu.n_items(), 4);
+        assert_eq!(menu.item_attribute_value(0, "label", None), Some("Increment".to_variant()));
+        assert_eq!(menu.item_attribute_value(0, "action", None), Some("win.main-menu-0".to_variant()));
+        let submenu = menu.item_link(3, "submenu").expect("submenu");
+        assert_eq!(submenu.item_attribute_value(0, "action", None), Some("win.quit".to_variant()));
+
+        assert!(!group.is_action_enabled("main-menu-1"));
+        group.activate_action("main-menu-0", None);
+        wait(10);
+        assert_text!(label, 1);
+        assert!(group.is_action_enabled("main-menu-1"));
+
+        group.activate_action("main-menu-1", None);
+        wait(10);
+        assert_text!(label, 0);
+        assert!(!group.is_action_enabled("main-menu-1"));
+    }
+}
@@ -0,0 +1,154 @@
+/*
+ * Copyright (c) 2018 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    prelude::ButtonExt,
+    prelude::GtkWindowExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{QuitPolicy, Relm, Widget, WindowEvent, WindowManager};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum SecondaryMsg {
+}
+
+#[widget]
+impl Widget for SecondaryWin {
+    fn model() {
+    }
+
+    fn update(&mut self, _msg: SecondaryMsg) {
+    }
+
+    view! {
+        gtk::Window {
+            title: "Secondary window",
+        }
+    }
+}
+
+pub struct Model {
+    open_windows: usize,
+    windows: WindowManager<Msg>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Open,
+    Windows(WindowEvent),
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        let _ = self.model.windows.add(&self.root());
+    }
+
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            open_windows: 0,
+            // Quit when the last window is closed.
+            windows: WindowManager::new(relm.stream().clone(), QuitPolicy::LastWindowClosed, Windows),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Open => {
+                if let Err(error) = self.model.windows.open::<SecondaryWin>(()) {
+                    eprintln!("Cannot open a window: {}", error);
+                }
+            },
+            Windows(WindowEvent::Opened(_)) => self.model.open_windows += 1,
+            Windows(WindowEvent::Closed(_)) => self.model.open_windows -= 1,
+        }
+    }
+
+    view! {
+        gtk::Window {
+            title: "Main window",
+            gtk::Box {
+                orientation: Vertical,
+                #[name="open_button"]
+                gtk::Button {
+                    clicked => Open,
+                    label: "Open a window",
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.open_windows.to_string(),
+                },
+            },
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use glib::Cast;
+    use gtk::prelude::{GtkWindowExt, LabelExt};
+
+    use gtk_test::{assert_text, wait};
+    use relm_test::click;
+
+    use crate::Win;
+
+    fn close_windows(title: Option<&str>) {
+        for window in gtk::Window::list_toplevels() {
+            if let Ok(window) = window.downcast::<gtk::Window>() {
+                if title.is_none() || window.title().as_deref() == title {
+                    window.close();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn open_windows() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let label = &widgets.label;
+        wait(10);
+        assert_text!(label, 1);
+
+        click(&widgets.open_button);
+        wait(10);
+        assert_text!(label, 2);
+
+        close_windows(Some("Secondary window"));
+        wait(10);
+        assert_text!(label, 1);
+    }
+
+    #[test]
+    fn quit_when_last_window_closed() {
+        glib::idle_add_local_once(|| close_windows(None));
+        relm::run::<Win>(()).expect("run failed");
+    }
+}
@@ -0,0 +1,253 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct List {
+    items: Vec<String>,
+}
+
+impl List {
+    fn add_item(&mut self, item: String) {
+        self.items.push(item);
+    }
+
+    fn text(&self) -> String {
+        self.items.join(", ")
+    }
+}
+
+trait Shout {
+    fn shout(&mut self);
+}
+
+impl Shout for String {
+    fn shout(&mut self) {
+        *self = self.to_uppercase();
+    }
+}
+
+pub struct Model {
+    items: Vec<String>,
+    list: List,
+    selected: Option<String>,
+    text: String,
+}
+
+impl Model {
+    fn reset(&mut self) {
+        self.items.clear();
+        self.list.items.clear();
+        self.text.clear();
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    AddItem(String),
+    Append(String),
+    AppendThroughReference(String),
+    Clear,
+    Push(String),
+    Quit,
+    Reset,
+    RetainShort,
+    Select(String),
+    ShoutSelected,
+    ShoutText,
+    Upper,
+}
+
+fn clear(text: &mut String) {
+    text.clear();
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            items: vec![],
+            list: List {
+                items: vec![],
+            },
+            selected: None,
+            text: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            AddItem(item) => self.model.list.add_item(item),
+            Append(text) => self.model.text.push_str(&text),
+            AppendThroughReference(text) => {
+                let current_text = &mut self.model.text;
+                current_text.push_str(&text);
+            },
+            Clear => clear(&mut self.model.text),
+            Push(item) => self.model.items.push(item),
+            Quit => gtk::main_quit(),
+            Reset => self.model.reset(),
+            RetainShort => self.model.items.retain(|item| item.len() <= 3),
+            Select(item) => self.model.selected = Some(item),
+            ShoutSelected => {
+                if let Some(ref mut selected) = self.model.selected {
+                    selected.shout();
+                }
+            },
+            ShoutText => self.model.text.shout(),
+            Upper => {
+                for item in self.model.items.iter_mut() {
+                    *item = item.to_uppercase();
+                }
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="text_label"]
+                gtk::Label {
+                    text: &self.model.text,
+                },
+                #[name="items_label"]
+                gtk::Label {
+                    text: &self.model.items.join(", "),
+                },
+                #[name="list_label"]
+                gtk::Label {
+                    text: &self.model.list.text(),
+                },
+                #[name="selected_label"]
+                gtk::Label {
+                    text: self.model.selected.as_deref().unwrap_or(""),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::prelude::LabelExt;
+
+    use gtk_test::{assert_text, wait};
+
+    use crate::Msg::{
+        AddItem,
+        Append,
+        AppendThroughReference,
+        Clear,
+        Push,
+        Reset,
+        RetainShort,
+        Select,
+        ShoutSelected,
+        ShoutText,
+        Upper,
+    };
+    use crate::Win;
+
+    #[test]
+    fn in_place_mutations() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let text_label = &widgets.text_label;
+        let items_label = &widgets.items_label;
+
+        component.emit(Append("Hello".to_string()));
+        component.emit(Append(" World".to_string()));
+        wait(10);
+        assert_text!(text_label, "Hello World");
+
+        component.emit(Clear);
+        wait(10);
+        assert_text!(text_label, "");
+
+        component.emit(Push("one".to_string()));
+        component.emit(Push("two".to_string()));
+        wait(10);
+        assert_text!(items_label, "one, two");
+
+        component.emit(Upper);
+        wait(10);
+        assert_text!(items_label, "ONE, TWO");
+    }
+
+    #[test]
+    fn borrowed_mutations() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let text_label = &widgets.text_label;
+        let items_label = &widgets.items_label;
+        let list_label = &widgets.list_label;
+        let selected_label = &widgets.selected_label;
+
+        // Mutation through a mutable reference bound to a variable.
+        component.emit(AppendThroughReference("hello".to_string()));
+        wait(10);
+        assert_text!(text_label, "hello");
+
+        // Method of a trait taking `&mut self`.
+        component.emit(ShoutText);
+        wait(10);
+        assert_text!(text_label, "HELLO");
+
+        // Custom method of an attribute taking `&mut self`.
+        component.emit(AddItem("first".to_string()));
+        component.emit(AddItem("second".to_string()));
+        wait(10);
+        assert_text!(list_label, "first, second");
+
+        component.emit(Push("one".to_string()));
+        component.emit(Push("three".to_string()));
+        component.emit(RetainShort);
+        wait(10);
+        assert_text!(items_label, "one");
+
+        // Mutation through a `ref mut` binding.
+        component.emit(Select("item".to_string()));
+        component.emit(ShoutSelected);
+        wait(10);
+        assert_text!(selected_label, "ITEM");
+
+        // Method of the model taking `&mut self`, which can mutate every attribute.
+        component.emit(Reset);
+        wait(10);
+        assert_text!(text_label, "");
+        assert_text!(items_label, "");
+        assert_text!(list_label, "");
+    }
+}
@@ -0,0 +1,158 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::ButtonExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::CounterMsg::*;
+use self::Msg::*;
+
+pub struct CounterModel {
+    counter: i32,
+    step: i32,
+    title: String,
+}
+
+#[derive(Msg)]
+pub enum CounterMsg {
+    Increment,
+}
+
+#[widget]
+impl Widget for Counter {
+    // Every parameter has a default value, so a parent can only specify some of them by name.
+    fn model(#[default("Counter".to_string())] title: String, #[default(1)] step: i32, start: i32) -> CounterModel {
+        CounterModel {
+            counter: start,
+            step,
+            title,
+        }
+    }
+
+    fn update(&mut self, event: CounterMsg) {
+        match event {
+            Increment => self.model.counter += self.model.step,
+        }
+    }
+
+    view! {
+        gtk::Box {
+            orientation: Vertical,
+            #[name="title"]
+            gtk::Label {
+                text: &self.model.title,
+            },
+            #[name="inc_button"]
+            gtk::Button {
+                clicked => Increment,
+                label: "+",
+            },
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.counter.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                #[name="default_counter"]
+                Counter,
+                #[name="titled_counter"]
+                Counter(title: "Score"),
+                #[name="step_counter"]
+                Counter(step: 10, start: 100),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{Button, Label, prelude::LabelExt};
+
+    use gtk_test::{assert_text, find_child_by_name};
+    use relm_test::click;
+
+    use crate::{Counter, CounterParams, Win};
+
+    #[test]
+    fn named_params() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let default_title: Label = find_child_by_name(&widgets.default_counter, "title").expect("default title");
+        let default_label: Label = find_child_by_name(&widgets.default_counter, "label").expect("default label");
+        let titled_title: Label = find_child_by_name(&widgets.titled_counter, "title").expect("titled title");
+        let step_title: Label = find_child_by_name(&widgets.step_counter, "title").expect("step title");
+        let step_label: Label = find_child_by_name(&widgets.step_counter, "label").expect("step label");
+        let step_button: Button = find_child_by_name(&widgets.step_counter, "inc_button").expect("step button");
+
+        assert_text!(default_title, "Counter");
+        assert_text!(default_label, 0);
+        assert_text!(titled_title, "Score");
+        assert_text!(step_title, "Counter");
+        assert_text!(step_label, 100);
+
+        click(&step_button);
+        assert_text!(step_label, 110);
+    }
+
+    #[test]
+    fn default_params() {
+        let (_component, _, widgets) = relm::init_test::<Counter>(CounterParams {
+            start: 5,
+            ..Default::default()
+        }).expect("init_test failed");
+
+        click(&widgets.inc_button);
+        assert_text!(widgets.label, 6);
+    }
+}
@@ -0,0 +1,122 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::GtkWindowExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Horizontal;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => relm::quit(()),
+        }
+    }
+
+    view! {
+        // The size of the window and the position of the pane are restored when the application
+        // starts again.
+        #[persist = "main-window"]
+        gtk::Window {
+            default_width: 300,
+            default_height: 200,
+            #[name="paned"]
+            #[persist = "sidebar"]
+            gtk::Paned {
+                orientation: Horizontal,
+                gtk::Label {
+                    text: "Sidebar",
+                },
+                gtk::Label {
+                    text: "Content",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use glib::KeyFile;
+    use gtk::prelude::{GtkWindowExt, PanedExt, WidgetExtManual};
+
+    use gtk_test::wait;
+
+    use crate::Win;
+
+    #[test]
+    fn persist() {
+        let path = env::temp_dir().join(format!("relm-persist-{}", process::id())).join("state.ini");
+        relm::set_state_file(&path);
+
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let window = component.widget().clone();
+        assert_eq!(window.default_size(), (300, 200));
+        widgets.paned.set_position(120);
+        window.resize(400, 250);
+        wait(100);
+        drop(component);
+        // The state is saved when the widgets are destroyed.
+        unsafe {
+            window.destroy();
+        }
+
+        let file = KeyFile::new();
+        file.load_from_file(&path, glib::KeyFileFlags::NONE).expect("state file");
+        assert_eq!(file.integer("sidebar", "position").ok(), Some(120));
+        assert!(!file.boolean("main-window", "maximized").unwrap_or(false));
+
+        // The state is restored when the widgets are created again.
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        assert_eq!(widgets.paned.position(), 120);
+        if let (Ok(width), Ok(height)) = (file.integer("main-window", "width"), file.integer("main-window", "height")) {
+            assert_eq!(component.widget().default_size(), (width, height));
+        }
+
+        fs::remove_dir_all(path.parent().expect("directory")).expect("remove state directory");
+    }
+}
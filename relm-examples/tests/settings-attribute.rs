@@ -0,0 +1,165 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::ButtonExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{MemoryStore, Settings, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    dark_theme: bool,
+    font_size: i32,
+    settings: Settings,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    DarkThemeChanged(bool),
+    Increase,
+    Quit,
+    ToggleDarkTheme,
+}
+
+#[widget]
+impl Widget for Win {
+    // The values of the settings override the ones of the model.
+    fn model(store: MemoryStore) -> Model {
+        Model {
+            dark_theme: false,
+            font_size: 10,
+            settings: Settings::new(store),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            DarkThemeChanged(dark_theme) => self.model.dark_theme = dark_theme,
+            Increase => self.model.font_size += 1,
+            Quit => relm::quit(()),
+            ToggleDarkTheme => self.model.dark_theme = !self.model.dark_theme,
+        }
+    }
+
+    settings! {
+        self.model.settings,
+        dark_theme: "dark-theme" => DarkThemeChanged,
+        font_size: "font-size",
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="dark_theme_button"]
+                gtk::Button {
+                    clicked => ToggleDarkTheme,
+                    label: "Dark theme",
+                },
+                #[name="increase_button"]
+                gtk::Button {
+                    clicked => Increase,
+                    label: "+",
+                },
+                #[name="dark_theme_label"]
+                gtk::Label {
+                    text: &self.model.dark_theme.to_string(),
+                },
+                #[name="font_size_label"]
+                gtk::Label {
+                    text: &self.model.font_size.to_string(),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(MemoryStore::new()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use glib::ToVariant;
+    use gtk::prelude::LabelExt;
+    use relm::{MemoryStore, Settings, SettingsStore};
+
+    use gtk_test::{assert_text, click, wait};
+
+    use crate::Win;
+
+    #[test]
+    fn settings() {
+        let store = MemoryStore::new();
+        store.set("font-size", &12);
+        let (_component, _, widgets) = relm::init_test::<Win>(store.clone()).expect("init_test failed");
+        let dark_theme_label = &widgets.dark_theme_label;
+        let font_size_label = &widgets.font_size_label;
+
+        assert_text!(dark_theme_label, false);
+        assert_text!(font_size_label, 12);
+        assert_eq!(store.read("dark-theme"), None);
+
+        click(&widgets.increase_button);
+        wait(10);
+        assert_text!(font_size_label, 13);
+        assert_eq!(store.read("font-size"), Some(13.to_variant()));
+
+        click(&widgets.dark_theme_button);
+        wait(10);
+        assert_text!(dark_theme_label, true);
+        assert_eq!(store.read("dark-theme"), Some(true.to_variant()));
+
+        // A change made outside of the component is sent as a message.
+        store.set("dark-theme", &false);
+        wait(10);
+        assert_text!(dark_theme_label, false);
+    }
+
+    #[test]
+    fn key_file() {
+        let path = env::temp_dir().join(format!("relm-settings-{}", process::id())).join("settings.ini");
+        let settings = Settings::key_file(&path, "window");
+        assert_eq!(settings.get::<i32>("width"), None);
+        settings.set("width", &640);
+        settings.set("title", &"relm");
+
+        let settings = Settings::key_file(&path, "window");
+        assert_eq!(settings.get("width"), Some(640));
+        assert_eq!(settings.get("title"), Some("relm".to_string()));
+        // A value of another type is ignored.
+        assert_eq!(settings.get::<bool>("width"), None);
+
+        fs::remove_dir_all(path.parent().expect("directory")).expect("remove settings directory");
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::path::PathBuf;
+
+use gtk::{
+    ButtonsType,
+    Inhibit,
+    MessageType,
+    ResponseType,
+    gdk::RGBA,
+    prelude::ButtonExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{ColorDialog, FileDialog, MessageBox, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    relm: Relm<Win>,
+    text: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    AskColor,
+    AskDelete,
+    AskFile,
+    Color(Option<RGBA>),
+    Delete(ResponseType),
+    File(Option<PathBuf>),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            relm: relm.clone(),
+            text: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            AskColor =>
+                ColorDialog::new()
+                    .parent(&self.root())
+                    .title("Choose a color")
+                    .rgba(&RGBA::new(1.0, 0.0, 0.0, 1.0))
+                    .show(self.model.relm.stream(), Color),
+            AskDelete =>
+                MessageBox::new(MessageType::Question, ButtonsType::YesNo, "Delete the file?")
+                    .parent(&self.root())
+                    .modal(true)
+                    .title("Delete")
+                    .button("Move to the trash", ResponseType::Other(1))
+                    .show(self.model.relm.stream(), Delete),
+            AskFile =>
+                FileDialog::open()
+                    .parent(&self.root())
+                    .title("Open a file")
+                    .show(self.model.relm.stream(), File),
+            Color(color) => self.model.text = format!("{:?}", color.map(|color| color.to_string())),
+            Delete(response) => self.model.text = format!("{:?}", response),
+            File(file) => self.model.text = format!("{:?}", file),
+            Quit => relm::quit(()),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="color_button"]
+                gtk::Button {
+                    clicked => AskColor,
+                    label: "Color",
+                },
+                #[name="delete_button"]
+                gtk::Button {
+                    clicked => AskDelete,
+                    label: "Delete",
+                },
+                #[name="file_button"]
+                gtk::Button {
+                    clicked => AskFile,
+                    label: "File",
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.text,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{Dialog, ResponseType, Window};
+    use gtk::prelude::{Cast, DialogExt, GtkWindowExt, LabelExt};
+
+    use gtk_test::{assert_text, click, wait};
+
+    use crate::Win;
+
+    fn find_dialog(title: &str) -> Option<Dialog> {
+        Window::list_toplevels().into_iter()
+            .filter_map(|widget| widget.downcast::<Dialog>().ok())
+            .find(|dialog| dialog.title().map(|dialog_title| dialog_title == title).unwrap_or(false))
+    }
+
+    #[test]
+    fn standard_dialogs() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let label = &widgets.label;
+
+        click(&widgets.delete_button);
+        wait(10);
+        let dialog = find_dialog("Delete").expect("message dialog");
+        assert!(dialog.is_modal());
+        assert_eq!(dialog.transient_for().as_ref(), Some(component.widget()));
+        dialog.response(ResponseType::Other(1));
+        wait(10);
+        assert_text!(label, "Other(1)");
+        assert!(find_dialog("Delete").is_none());
+
+        click(&widgets.delete_button);
+        wait(10);
+        find_dialog("Delete").expect("message dialog").response(ResponseType::Yes);
+        wait(10);
+        assert_text!(label, "Yes");
+
+        click(&widgets.color_button);
+        wait(10);
+        let dialog = find_dialog("Choose a color").expect("color dialog");
+        assert!(!dialog.is_modal());
+        dialog.response(ResponseType::Ok);
+        wait(10);
+        assert_text!(label, "Some(\"rgb(255,0,0)\")");
+
+        click(&widgets.color_button);
+        wait(10);
+        find_dialog("Choose a color").expect("color dialog").response(ResponseType::Cancel);
+        wait(10);
+        assert_text!(label, "None");
+
+        click(&widgets.file_button);
+        wait(10);
+        assert_text!(label, "None");
+        let dialog = find_dialog("Open a file").expect("file dialog");
+        // Closing the dialog cancels it.
+        dialog.close();
+        wait(10);
+        assert_text!(label, "None");
+        assert!(find_dialog("Open a file").is_none());
+    }
+}
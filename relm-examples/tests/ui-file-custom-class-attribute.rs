@@ -0,0 +1,125 @@
+/*
+ * Copyright (c) 2026-2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    prelude::LabelExt,
+    prelude::StaticType,
+    prelude::WidgetExt,
+};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+// A custom widget, which the #[widget] attribute cannot create from the GtkBuilder file.
+mod custom {
+    mod imp {
+        use gtk::subclass::prelude::{BoxImpl, ContainerImpl, ObjectImpl, ObjectSubclass, WidgetImpl};
+
+        #[derive(Default)]
+        pub struct Panel;
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for Panel {
+            const NAME: &'static str = "RelmTestPanel";
+            type Type = super::Panel;
+            type ParentType = gtk::Box;
+        }
+
+        impl ObjectImpl for Panel {}
+        impl WidgetImpl for Panel {}
+        impl ContainerImpl for Panel {}
+        impl BoxImpl for Panel {}
+    }
+
+    glib::wrapper! {
+        pub struct Panel(ObjectSubclass<imp::Panel>)
+            @extends gtk::Box, gtk::Container, gtk::Widget,
+            @implements gtk::Orientable;
+    }
+}
+
+pub struct Model {
+    counter: i32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Increment,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        // Register the custom class before the GtkBuilder file is loaded.
+        let _ = custom::Panel::static_type();
+        Model {
+            counter: 0,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Increment => self.model.counter += 1,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    // The object of the custom class is not used in the view, so it is skipped.
+    view! {
+        "tests/custom-class.ui",
+        window {
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        },
+        counter_label {
+            text: &self.model.counter.to_string(),
+        },
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::prelude::{LabelExt, ObjectExt, WidgetExt};
+
+    use gtk_test::{assert_text, wait};
+
+    use crate::Msg::Increment;
+    use crate::Win;
+
+    #[test]
+    fn ui_file_with_custom_class() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let label = &widgets.counter_label;
+
+        assert_text!(label, 0);
+        assert_eq!(label.parent().expect("label parent").type_().name(), "RelmTestPanel");
+
+        component.emit(Increment);
+        wait(10);
+        assert_text!(label, 1);
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Adjustment,
+    Inhibit,
+    prelude::ButtonExt,
+    prelude::LabelExt,
+    prelude::OrientableExt,
+    prelude::RangeExt,
+    prelude::SpinButtonExt,
+    prelude::WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="check_button"]
+                gtk::CheckButton {
+                    label: "Enable",
+                },
+                #[name="button"]
+                gtk::Button {
+                    label: "Send",
+                    sensitive: bind check_button.active,
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: "Disabled",
+                    visible: bind check_button.active => |_, active: bool| Some(!active),
+                },
+                #[name="spin_button"]
+                gtk::SpinButton {
+                    adjustment: &Adjustment::new(0.0, 0.0, 100.0, 1.0, 10.0, 0.0),
+                },
+                #[name="scale"]
+                gtk::Scale {
+                    adjustment: &Adjustment::new(0.0, 0.0, 1.0, 0.01, 0.1, 0.0),
+                    value: bind(bidirectional) spin_button.value =>
+                        (|_, value: f64| Some(value / 100.0), |_, value: f64| Some(value * 100.0)),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::prelude::{RangeExt, SpinButtonExt, ToggleButtonExt, WidgetExt};
+
+    use crate::Win;
+
+    #[test]
+    fn widget_bindings() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let check_button = &widgets.check_button;
+        let button = &widgets.button;
+        let label = &widgets.label;
+        let spin_button = &widgets.spin_button;
+        let scale = &widgets.scale;
+
+        assert!(!button.is_sensitive());
+        assert!(label.is_visible());
+
+        check_button.set_active(true);
+        assert!(button.is_sensitive());
+        assert!(!label.is_visible());
+
+        spin_button.set_value(50.0);
+        assert_eq!(scale.value(), 0.5);
+
+        scale.set_value(0.25);
+        assert_eq!(spin_button.value(), 25.0);
+    }
+}
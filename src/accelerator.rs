@@ -0,0 +1,161 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Keyboard accelerators of a component, scoped to its toplevel window.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk::gdk;
+use gtk::prelude::{AccelGroupExtManual, Cast, GtkWindowExt, ObjectExt, WidgetExt};
+use glib::{IsA, SignalHandlerId, WeakRef};
+
+use crate::StreamHandle;
+
+/// A keyboard accelerator declared by a component.
+#[derive(Clone, Debug)]
+pub struct Shortcut {
+    accelerator: String,
+    description: Option<String>,
+    key: u32,
+    modifiers: gdk::ModifierType,
+}
+
+impl Shortcut {
+    /// Get the accelerator, as it was declared (like `<Control>s`).
+    pub fn accelerator(&self) -> &str {
+        &self.accelerator
+    }
+
+    /// Get the description of the accelerator, to show it in a help dialog.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the key and the modifiers of the accelerator.
+    pub fn key(&self) -> (u32, gdk::ModifierType) {
+        (self.key, self.modifiers)
+    }
+
+    /// Get the accelerator, formatted to be shown to the user (like `Ctrl+S`).
+    pub fn label(&self) -> String {
+        gtk::accelerator_get_label(self.key, self.modifiers)
+            .map(|label| label.to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// The keyboard accelerators of a component, which emit messages.
+///
+/// They are active in the toplevel window of the widget they are created for, even when this
+/// widget is added to a window later.
+/// They are removed from the window when this value is dropped, with the component.
+pub struct Accelerators<MSG> {
+    group: gtk::AccelGroup,
+    handler: Option<SignalHandlerId>,
+    shortcuts: RefCell<Vec<Shortcut>>,
+    stream: StreamHandle<MSG>,
+    widget: WeakRef<gtk::Widget>,
+    window: Rc<RefCell<WeakRef<gtk::Window>>>,
+}
+
+impl<MSG: 'static> Accelerators<MSG> {
+    /// Create the accelerators of the toplevel window of `widget`.
+    pub fn new<WIDGET: IsA<gtk::Widget>>(widget: &WIDGET, stream: StreamHandle<MSG>) -> Self {
+        let group = gtk::AccelGroup::new();
+        let window = Rc::new(RefCell::new(WeakRef::new()));
+        attach(widget.upcast_ref(), &group, &window);
+        let handler = {
+            let group = group.clone();
+            let window = window.clone();
+            widget.connect_hierarchy_changed(move |widget, _| attach(widget.upcast_ref(), &group, &window))
+        };
+        Accelerators {
+            group,
+            handler: Some(handler),
+            shortcuts: RefCell::new(vec![]),
+            stream,
+            widget: widget.upcast_ref::<gtk::Widget>().downgrade(),
+            window,
+        }
+    }
+
+    /// Add an accelerator, like `<Control>s`, which emits the message returned by `callback`.
+    pub fn add<CALLBACK>(&self, accelerator: &str, description: Option<&str>, callback: CALLBACK)
+        where CALLBACK: Fn() -> MSG + 'static,
+    {
+        let (key, modifiers) = gtk::accelerator_parse(accelerator);
+        if key == 0 {
+            log::warn!("Invalid accelerator {}", accelerator);
+            return;
+        }
+        let stream = self.stream.clone();
+        let _ = self.group.connect_accel_group(key, modifiers, gtk::AccelFlags::VISIBLE, move |_, _, _, _| {
+            stream.emit(callback());
+            true
+        });
+        self.shortcuts.borrow_mut().push(Shortcut {
+            accelerator: accelerator.to_string(),
+            description: description.map(ToString::to_string),
+            key,
+            modifiers,
+        });
+    }
+
+    /// Get the accelerator group added to the window.
+    pub fn group(&self) -> &gtk::AccelGroup {
+        &self.group
+    }
+
+    /// Get the accelerators, in the order they were added.
+    pub fn shortcuts(&self) -> Vec<Shortcut> {
+        self.shortcuts.borrow().clone()
+    }
+}
+
+impl<MSG> Drop for Accelerators<MSG> {
+    fn drop(&mut self) {
+        if let (Some(widget), Some(handler)) = (self.widget.upgrade(), self.handler.take()) {
+            widget.disconnect(handler);
+        }
+        if let Some(window) = self.window.borrow().upgrade() {
+            window.remove_accel_group(&self.group);
+        }
+    }
+}
+
+/// Move the accelerator group to the toplevel window of `widget`.
+fn attach(widget: &gtk::Widget, group: &gtk::AccelGroup, window: &RefCell<WeakRef<gtk::Window>>) {
+    let toplevel = widget.toplevel()
+        .filter(|toplevel| toplevel.is_toplevel())
+        .and_then(|toplevel| toplevel.downcast::<gtk::Window>().ok());
+    let old_window = window.borrow().upgrade();
+    if old_window == toplevel {
+        return;
+    }
+    if let Some(old_window) = old_window {
+        old_window.remove_accel_group(group);
+    }
+    if let Some(ref toplevel) = toplevel {
+        toplevel.add_accel_group(group);
+    }
+    *window.borrow_mut() = toplevel.map(|toplevel| toplevel.downgrade()).unwrap_or_default();
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Actions installed on a widget that emit messages when they are activated.
+
+use gtk::gio;
+use gtk::prelude::{ActionExt, ActionMapExt, WidgetExt};
+use glib::{FromVariant, IsA, StaticVariantType, ToVariant, VariantTy};
+
+use crate::StreamHandle;
+
+/// The actions of a component, installed on a widget as a `gio::SimpleActionGroup`.
+///
+/// The actions are activated by the widgets with an action name (`prefix.name`), like the menu
+/// items and the buttons, and by the keyboard accelerators.
+/// Every activation emits a message to the stream of the component.
+pub struct Actions<MSG> {
+    group: gio::SimpleActionGroup,
+    prefix: String,
+    stream: StreamHandle<MSG>,
+}
+
+impl<MSG: 'static> Actions<MSG> {
+    /// Install a new action group on `widget`, whose actions are named `prefix.name`.
+    pub fn new<WIDGET: IsA<gtk::Widget>>(widget: &WIDGET, prefix: &str, stream: StreamHandle<MSG>) -> Self {
+        let group = gio::SimpleActionGroup::new();
+        widget.insert_action_group(prefix, Some(&group));
+        Actions {
+            group,
+            prefix: prefix.to_string(),
+            stream,
+        }
+    }
+
+    /// Add an action without parameter which emits the message returned by `callback`.
+    pub fn add<CALLBACK>(&self, name: &str, callback: CALLBACK)
+        where CALLBACK: Fn() -> MSG + 'static,
+    {
+        let action = gio::SimpleAction::new(name, None);
+        let stream = self.stream.clone();
+        action.connect_activate(move |_, _| stream.emit(callback()));
+        self.group.add_action(&action);
+    }
+
+    /// Add an action taking a parameter of type `PARAM` which is given to `callback`.
+    pub fn add_with_parameter<PARAM, CALLBACK>(&self, name: &str, callback: CALLBACK)
+        where CALLBACK: Fn(PARAM) -> MSG + 'static,
+              PARAM: FromVariant + StaticVariantType,
+    {
+        let action = gio::SimpleAction::new(name, Some(&PARAM::static_variant_type()));
+        let stream = self.stream.clone();
+        action.connect_activate(move |action, parameter| {
+            match parameter.and_then(PARAM::from_variant) {
+                Some(parameter) => stream.emit(callback(parameter)),
+                None => log::warn!("Invalid parameter for the action {}", action.name()),
+            }
+        });
+        self.group.add_action(&action);
+    }
+
+    /// Add an action with a state.
+    /// A `bool` state is toggled when the action is activated, like a check menu item.
+    /// The other states are changed to the parameter of the action, which has the same type,
+    /// like a radio menu item.
+    ///
+    /// The new state is given to `callback`, but it is only shown after it is set with
+    /// [`set_state()`](#method.set_state), so that it stays in sync with the model.
+    pub fn add_stateful<STATE, CALLBACK>(&self, name: &str, state: &STATE, callback: CALLBACK)
+        where CALLBACK: Fn(STATE) -> MSG + 'static,
+              STATE: FromVariant + StaticVariantType + ToVariant,
+    {
+        let state_type = STATE::static_variant_type();
+        let parameter_type =
+            if *state_type == *VariantTy::BOOLEAN {
+                None
+            }
+            else {
+                Some(&*state_type)
+            };
+        let action = gio::SimpleAction::new_stateful(name, parameter_type, &state.to_variant());
+        let stream = self.stream.clone();
+        action.connect_change_state(move |action, state| {
+            match state.and_then(STATE::from_variant) {
+                Some(state) => stream.emit(callback(state)),
+                None => log::warn!("Invalid state for the action {}", action.name()),
+            }
+        });
+        self.group.add_action(&action);
+    }
+
+    /// Get the action group installed on the widget.
+    pub fn group(&self) -> &gio::SimpleActionGroup {
+        &self.group
+    }
+
+    /// Get the prefix of the actions.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Enable or disable the action `name`.
+    pub fn set_enabled(&self, name: &str, enabled: bool) {
+        if let Some(action) = self.lookup(name) {
+            action.set_enabled(enabled);
+        }
+    }
+
+    /// Set the state of the stateful action `name`.
+    pub fn set_state<STATE: ToVariant + ?Sized>(&self, name: &str, state: &STATE) {
+        if let Some(action) = self.lookup(name) {
+            action.set_state(&state.to_variant());
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<gio::SimpleAction> {
+        let action = self.group.lookup_action(name)
+            .and_then(|action| glib::Cast::downcast::<gio::SimpleAction>(action).ok());
+        if action.is_none() {
+            log::warn!("No action {}.{}", self.prefix, name);
+        }
+        action
+    }
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Run a relm widget as the main window of a `gtk::Application`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk::gio;
+use gtk::prelude::{ApplicationCommandLineExt, ApplicationExt, ApplicationExtManual, GtkApplicationExt, GtkWindowExt};
+use glib::IsA;
+
+use crate::{Component, StreamHandle, Widget, init};
+
+struct State<WIDGET: Widget> {
+    component: RefCell<Option<Component<WIDGET>>>,
+    model_param: RefCell<Option<WIDGET::ModelParam>>,
+}
+
+impl<WIDGET> State<WIDGET>
+    where WIDGET: Widget + 'static,
+          WIDGET::Root: IsA<gtk::Window>,
+{
+    /// Create the root widget the first time the application is activated and show its window
+    /// the next times.
+    fn activate(&self, application: &gtk::Application) -> Option<StreamHandle<WIDGET::Msg>> {
+        let mut component = self.component.borrow_mut();
+        if let Some(ref component) = *component {
+            component.widget().present();
+            return Some(component.stream());
+        }
+        let model_param = self.model_param.borrow_mut().take()?;
+        match init::<WIDGET>(model_param) {
+            Ok(new_component) => {
+                application.add_window(new_component.widget());
+                let stream = new_component.stream();
+                *component = Some(new_component);
+                Some(stream)
+            },
+            Err(error) => {
+                log::error!("Cannot create the root widget of the application: {}", error);
+                None
+            },
+        }
+    }
+}
+
+/// Create the specified relm `Widget` when the `gtk::Application` with the id `app_id` is
+/// activated and run this application.
+///
+/// The window of the widget is added to the application, which quits when this window is closed.
+/// When the application is launched again, the existing window is shown instead (see
+/// `gio::ApplicationFlags` to change this behaviour).
+/// The files to open (with the `HANDLES_OPEN` flag) and the command lines (with the
+/// `HANDLES_COMMAND_LINE` flag) are sent to the widget with the messages returned by
+/// [`Widget::open()`](trait.Widget.html#method.open) and
+/// [`Widget::command_line()`](trait.Widget.html#method.command_line).
+///
+/// Return the exit status of the application, which can be set in
+/// [`Widget::command_line()`](trait.Widget.html#method.command_line).
+pub fn run_application<WIDGET>(app_id: &str, flags: gio::ApplicationFlags, model_param: WIDGET::ModelParam) -> i32
+    where WIDGET: Widget + 'static,
+          WIDGET::Root: IsA<gtk::Window>,
+{
+    let args: Vec<String> = std::env::args().collect();
+    run_application_with_args::<WIDGET, _>(app_id, flags, model_param, &args)
+}
+
+/// Like [`run_application()`](fn.run_application.html), but with the command line arguments
+/// `args` instead of the ones of the process.
+pub fn run_application_with_args<WIDGET, S>(app_id: &str, flags: gio::ApplicationFlags, model_param: WIDGET::ModelParam,
+    args: &[S]) -> i32
+    where S: AsRef<str>,
+          WIDGET: Widget + 'static,
+          WIDGET::Root: IsA<gtk::Window>,
+{
+    let application = gtk::Application::new(Some(app_id), flags);
+    let state = Rc::new(State::<WIDGET> {
+        component: RefCell::new(None),
+        model_param: RefCell::new(Some(model_param)),
+    });
+
+    {
+        let state = state.clone();
+        application.connect_activate(move |application| {
+            let _ = state.activate(application);
+        });
+    }
+
+    {
+        let state = state.clone();
+        application.connect_open(move |application, files, hint| {
+            if let Some(stream) = state.activate(application) {
+                if let Some(msg) = WIDGET::open(files.to_vec(), hint.to_string()) {
+                    stream.emit(msg);
+                }
+            }
+        });
+    }
+
+    {
+        let state = state.clone();
+        application.connect_command_line(move |application, command_line| {
+            if let Some(stream) = state.activate(application) {
+                if let Some(msg) = WIDGET::command_line(command_line.clone()) {
+                    stream.emit(msg);
+                }
+            }
+            // The widget can set the exit status in command_line().
+            command_line.exit_status()
+        });
+    }
+
+    {
+        let state = state.clone();
+        // Destroy the widget before the application is finalized.
+        application.connect_shutdown(move |_| {
+            let _ = state.component.borrow_mut().take();
+        });
+    }
+
+    application.run_with_args(args)
+}
@@ -0,0 +1,196 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Construction of the widgets of the `#[widget]` attribute with their construct properties.
+//!
+//! The construct properties are given to the typed builder of the widget, like
+//! `gtk::Button::builder()`, so that their names and their types are checked at compile time.
+//! When a property has no setter in the builder, like the `group` of a `gtk::RadioButton`, the
+//! whole widget is constructed with a `glib::object::ObjectBuilder` instead, with all its
+//! properties as `glib::Value`s, so that the construct-only properties are still given at
+//! construction. A widget without a builder, like a custom subclass, is always constructed with
+//! an `ObjectBuilder`.
+//!
+//! The fallbacks are selected by auto-ref: the code generated for a property `name` declares a
+//! trait with a method `name()` which is only called when the builder has no inherent method with
+//! this name.
+
+use std::cell::Cell;
+
+use glib::{IsA, Object, ToValue, Value};
+use glib::object::{IsClass, ObjectBuilder};
+
+/// Builder of the widgets which have no `builder()` function.
+#[doc(hidden)]
+pub trait ConstructBuilder: Sized {
+    #[doc(hidden)]
+    fn builder() -> ObjectBuilder<'static, Self>;
+}
+
+impl<WIDGET: IsA<Object> + IsClass> ConstructBuilder for WIDGET {
+    fn builder() -> ObjectBuilder<'static, Self> {
+        Object::builder()
+    }
+}
+
+/// Construct property which has no setter in the builder.
+#[doc(hidden)]
+pub struct ConstructProperty<BUILDER> {
+    builder: BUILDER,
+    name: &'static str,
+    value: Value,
+}
+
+impl<BUILDER> ConstructProperty<BUILDER> {
+    #[doc(hidden)]
+    pub fn new<VALUE: ToValue>(builder: BUILDER, name: &'static str, value: VALUE) -> Self {
+        ConstructProperty {
+            builder,
+            name,
+            value: value.to_value(),
+        }
+    }
+}
+
+/// Result of the call to the setter of a construct property: either the builder when the setter
+/// exists or a `ConstructProperty` otherwise.
+#[doc(hidden)]
+pub struct ConstructStep<STEP> {
+    step: Cell<Option<STEP>>,
+}
+
+impl<STEP> ConstructStep<STEP> {
+    #[doc(hidden)]
+    pub fn new(step: STEP) -> Self {
+        ConstructStep {
+            step: Cell::new(Some(step)),
+        }
+    }
+
+    fn take(&self) -> STEP {
+        self.step.take().expect("construct step")
+    }
+}
+
+/// Give the property to the `ObjectBuilder` used for the widgets without a builder.
+#[doc(hidden)]
+pub trait ObjectBuilderProperty {
+    #[doc(hidden)]
+    type Builder;
+
+    #[doc(hidden)]
+    fn apply(&self, pending_properties: &mut Vec<&'static str>) -> Self::Builder;
+}
+
+impl<WIDGET: IsA<Object> + IsClass> ObjectBuilderProperty for &ConstructStep<ConstructProperty<ObjectBuilder<'static, WIDGET>>> {
+    type Builder = ObjectBuilder<'static, WIDGET>;
+
+    fn apply(&self, _pending_properties: &mut Vec<&'static str>) -> Self::Builder {
+        let ConstructProperty { builder, name, value } = self.take();
+        builder.property(name, value)
+    }
+}
+
+/// Record the property missing from a typed builder, to construct the widget with an
+/// `ObjectBuilder` instead.
+#[doc(hidden)]
+pub trait PendingProperty {
+    #[doc(hidden)]
+    type Builder;
+
+    #[doc(hidden)]
+    fn apply(&self, pending_properties: &mut Vec<&'static str>) -> Self::Builder;
+}
+
+impl<BUILDER> PendingProperty for &&ConstructStep<ConstructProperty<BUILDER>> {
+    type Builder = BUILDER;
+
+    fn apply(&self, pending_properties: &mut Vec<&'static str>) -> Self::Builder {
+        let ConstructProperty { builder, name, .. } = self.take();
+        pending_properties.push(name);
+        builder
+    }
+}
+
+/// The setter of the typed builder was called: there is nothing left to do.
+#[doc(hidden)]
+pub trait BuilderProperty {
+    #[doc(hidden)]
+    type Builder;
+
+    #[doc(hidden)]
+    fn apply(&self, pending_properties: &mut Vec<&'static str>) -> Self::Builder;
+}
+
+impl<BUILDER> BuilderProperty for ConstructStep<BUILDER> {
+    type Builder = BUILDER;
+
+    fn apply(&self, _pending_properties: &mut Vec<&'static str>) -> Self::Builder {
+        self.take()
+    }
+}
+
+/// Value of a construct property, kept in case the widget is constructed with an `ObjectBuilder`.
+#[doc(hidden)]
+pub struct ConstructValue<'a, VALUE>(pub &'a VALUE);
+
+/// The value can be given to an `ObjectBuilder`.
+#[doc(hidden)]
+pub trait ToConstructValue {
+    #[doc(hidden)]
+    fn construct_value(&self) -> Option<Value>;
+}
+
+impl<VALUE: ToValue> ToConstructValue for &ConstructValue<'_, VALUE> {
+    fn construct_value(&self) -> Option<Value> {
+        Some(self.0.to_value())
+    }
+}
+
+/// The value is only accepted by the setter of the typed builder.
+#[doc(hidden)]
+pub trait NoConstructValue {
+    #[doc(hidden)]
+    fn construct_value(&self) -> Option<Value>;
+}
+
+impl<VALUE> NoConstructValue for ConstructValue<'_, VALUE> {
+    fn construct_value(&self) -> Option<Value> {
+        None
+    }
+}
+
+/// Construct the widget with all its construct properties, when some of them have no setter in
+/// its typed builder.
+#[doc(hidden)]
+pub fn build_with_values<WIDGET: IsA<Object> + IsClass>(values: Vec<(&'static str, Option<Value>)>,
+    pending_properties: &[&'static str]) -> WIDGET
+{
+    let mut builder = Object::builder::<WIDGET>();
+    for (name, value) in values {
+        match value {
+            Some(value) => builder = builder.property(name, value),
+            None => panic!("Cannot construct a {} with the properties {:?}: the value of the property {} is not a glib::Value",
+                WIDGET::static_type(), pending_properties, name),
+        }
+    }
+    builder.build()
+}
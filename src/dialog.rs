@@ -0,0 +1,174 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Show a widget as a modal dialog, which gives a result when it is closed.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use gtk::prelude::{Cast, GtkWindowExt, WidgetExt, WidgetExtManual};
+use glib::IsA;
+
+use crate::{Component, StreamHandle, Widget, init};
+
+/// A widget which can be shown as a modal dialog with [`dialog()`](fn.dialog.html).
+pub trait Dialog: Widget {
+    /// The result of the dialog.
+    type Output: 'static;
+
+    /// Get the result of the dialog when it receives `msg`, which closes it.
+    fn output(msg: &Self::Msg) -> Option<Self::Output>;
+
+    /// Get the result of the dialog when it is closed without a result, like with its close
+    /// button.
+    fn cancelled() -> Self::Output;
+}
+
+struct ResponseState<OUTPUT> {
+    callback: Option<Box<dyn FnOnce(OUTPUT)>>,
+    output: Option<OUTPUT>,
+    waker: Option<Waker>,
+}
+
+/// The future result of a dialog shown with [`dialog()`](fn.dialog.html).
+/// It can also be sent as a message with [`connect()`](#method.connect).
+#[must_use]
+pub struct Response<OUTPUT> {
+    state: Rc<RefCell<ResponseState<OUTPUT>>>,
+}
+
+impl<OUTPUT: 'static> Response<OUTPUT> {
+    /// Send the message returned by `callback` with the result of the dialog to `stream`.
+    pub fn connect<MSG, CALLBACK>(self, stream: &StreamHandle<MSG>, callback: CALLBACK)
+        where CALLBACK: FnOnce(OUTPUT) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let stream = stream.clone();
+        let mut state = self.state.borrow_mut();
+        match state.output.take() {
+            Some(output) => stream.emit(callback(output)),
+            None => state.callback = Some(Box::new(move |output| stream.emit(callback(output)))),
+        }
+    }
+
+    fn resolve(&self, output: OUTPUT) {
+        let callback = self.state.borrow_mut().callback.take();
+        match callback {
+            Some(callback) => callback(output),
+            None => {
+                let waker = {
+                    let mut state = self.state.borrow_mut();
+                    state.output = Some(output);
+                    state.waker.take()
+                };
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            },
+        }
+    }
+}
+
+impl<OUTPUT> Future for Response<OUTPUT> {
+    type Output = OUTPUT;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<OUTPUT> {
+        let mut state = self.state.borrow_mut();
+        match state.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                state.waker = Some(context.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+struct DialogState<DIALOG: Dialog> {
+    // Keep the component alive until the dialog is closed.
+    component: RefCell<Option<Component<DIALOG>>>,
+    response: Response<DIALOG::Output>,
+}
+
+impl<DIALOG: Dialog + 'static> DialogState<DIALOG> {
+    fn finish(&self, output: DIALOG::Output) {
+        let component =
+            match self.component.borrow_mut().take() {
+                Some(component) => component,
+                None => return,
+            };
+        self.response.resolve(output);
+        // The dialog may be processing an event or a message, so it is destroyed later.
+        let _ = glib::idle_add_local_once(move || {
+            let window = component.widget().clone();
+            // Safety: the component is dropped right after its window.
+            unsafe {
+                window.destroy();
+            }
+            drop(component);
+        });
+    }
+}
+
+/// Show the widget `DIALOG` as a modal dialog for `parent`, without running a nested main loop.
+///
+/// The dialog is closed when it receives a message for which
+/// [`Dialog::output()`](trait.Dialog.html#tymethod.output) gives a result, or when it is closed
+/// by the user.
+/// The returned [`Response`](struct.Response.html) can be awaited or sent as a message.
+pub fn dialog<DIALOG>(parent: &impl IsA<gtk::Window>, model_param: DIALOG::ModelParam)
+    -> Result<Response<DIALOG::Output>, glib::BoolError>
+    where DIALOG: Dialog + 'static,
+          DIALOG::Root: IsA<gtk::Window>,
+{
+    let component = init::<DIALOG>(model_param)?;
+    let window: gtk::Window = component.widget().clone().upcast();
+    window.set_transient_for(Some(parent));
+    window.set_modal(true);
+    window.set_destroy_with_parent(true);
+    let stream = component.stream();
+    let state = Rc::new(DialogState {
+        component: RefCell::new(Some(component)),
+        response: Response {
+            state: Rc::new(RefCell::new(ResponseState {
+                callback: None,
+                output: None,
+                waker: None,
+            })),
+        },
+    });
+    let response = Response {
+        state: state.response.state.clone(),
+    };
+    {
+        let state = state.clone();
+        stream.observe(move |msg| {
+            if let Some(output) = DIALOG::output(msg) {
+                state.finish(output);
+            }
+        });
+    }
+    let _ = window.connect_destroy(move |_| state.finish(DIALOG::cancelled()));
+    Ok(response)
+}
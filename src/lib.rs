@@ -73,15 +73,10 @@
  * TODO: remove the closure transformer code.
  *
  * TODO: move most of the examples in the tests/ directory.
- * TODO: add construct-only properties for relm widget (to replace initial parameters) to allow
- * setting them by name (or with default value).
- * TODO: find a way to do two-step initialization (to avoid using unitialized in model()).
- * TODO: consider using GBinding instead of manually adding calls to set_property().
  *
  * TODO: add a FAQ with the question related to getting an error when not importing the gtk traits.
  *
  * TODO: show a warning when components are destroyed after the end of call to Widget::view().
- * TODO: warn in the attribute when an event cycle is found.
  * TODO: add a Deref<Widget> for Component?
  * TODO: look at how Elm works with the <canvas> element.
  * TODO: the widget names should start with __relm_field_.
@@ -96,13 +91,27 @@
  * TODO: optionnaly multi-threaded.
  */
 
+mod accelerator;
+mod action;
+mod application;
 mod component;
+mod construct;
 mod container;
 mod core;
+mod dialog;
 mod drawing;
 mod macros;
+mod main_loop;
+mod persist;
+mod property;
+mod reload;
+mod settings;
+mod standard_dialog;
 mod state;
 mod widget;
+mod window_manager;
+
+use std::rc::Rc;
 
 #[doc(hidden)]
 pub use fragile::Fragile;
@@ -118,11 +127,34 @@ pub use glib::{
 };
 #[doc(hidden)]
 pub use glib::translate::{FromGlibPtrNone, IntoGlib, ToGlibPtr};
-#[doc(hidden)]
-pub use gobject_sys::{GParameter, g_object_newv};
 use glib::Continue;
+use gtk::prelude::WidgetExtManual;
 
+#[doc(hidden)]
+pub use crate::construct::{
+    BuilderProperty,
+    ConstructBuilder,
+    ConstructProperty,
+    ConstructStep,
+    ConstructValue,
+    NoConstructValue,
+    ObjectBuilderProperty,
+    PendingProperty,
+    ToConstructValue,
+    build_with_values,
+};
 pub use crate::core::{Channel, EventStream, Sender, StreamHandle};
+#[doc(hidden)]
+pub use crate::property::{
+    CachedPropertySetter,
+    PropertyCache,
+    PropertySetter,
+    PropertyValue,
+    UncachedPropertySetter,
+    update_binding,
+};
+#[doc(hidden)]
+pub use crate::reload::watch_view_file;
 pub use crate::state::{
     DisplayVariant,
     IntoOption,
@@ -134,10 +166,19 @@ pub use crate::state::{
 };
 use state::init_component;
 
+pub use accelerator::{Accelerators, Shortcut};
+pub use action::Actions;
+pub use application::{run_application, run_application_with_args};
 pub use component::Component;
 pub use container::{Container, ContainerComponent, ContainerWidget};
+pub use dialog::{Dialog, Response, dialog};
 pub use drawing::DrawHandler;
+pub use main_loop::{Exit, quit};
+pub use persist::{Persist, persist, set_state_file};
+pub use settings::{KeyFileStore, MemoryStore, Settings, SettingsStore};
+pub use standard_dialog::{ColorDialog, FileDialog, FontDialog, MessageBox};
 pub use widget::{Widget, WidgetTest};
+pub use window_manager::{QuitPolicy, WindowEvent, WindowId, WindowManager};
 
 /// Dummy macro to be used with `#[derive(Widget)]`.
 #[macro_export]
@@ -204,6 +245,9 @@ fn create_widget<WIDGET>(model_param: WIDGET::ModelParam)
     let model = WIDGET::model(&relm, model_param);
     let mut widget = WIDGET::view(&relm, model);
     widget.init_view();
+    for msg in widget.post_init(&relm) {
+        stream.emit(msg);
+    }
 
     let root = widget.root();
     (Component::new(stream, root), widget, relm)
@@ -339,15 +383,34 @@ pub fn init<WIDGET>(model_param: WIDGET::ModelParam) -> Result<Component<WIDGET>
 /// Win::run(()).expect("Win::run failed");
 /// # }
 /// ```
-pub fn run<WIDGET>(model_param: WIDGET::ModelParam) -> Result<(), glib::BoolError>
+///
+/// The main loop runs until [`quit()`](fn.quit.html) is called: the value given to it is then
+/// returned in the [`Exit`](struct.Exit.html), with the final model of the widget.
+/// `gtk::main_quit()` also stops it, without a value.
+/// The messages are not processed anymore when this function returns and the root widget is
+/// destroyed.
+pub fn run<WIDGET>(model_param: WIDGET::ModelParam) -> Result<Exit<WIDGET::Model>, glib::BoolError>
     where WIDGET: Widget + 'static,
 {
     let main_context = glib::MainContext::default();
     let _context = main_context.acquire()?;
     gtk::init()?;
-    let _component = init::<WIDGET>(model_param)?;
-    gtk::main();
-    Ok(())
+    let (component, widget, relm) = create_widget::<WIDGET>(model_param);
+    init_component::<WIDGET>(component.owned_stream(), widget, &relm);
+    let value = main_loop::run_main_loop();
+
+    let root = component.widget().clone();
+    let widget = relm.component();
+    // Close the stream first, so that no message is processed while the widgets are destroyed.
+    drop(component);
+    let model = widget
+        .and_then(|widget| Rc::try_unwrap(widget).ok())
+        .and_then(|widget| widget.into_inner().into_model());
+    // Safety: the component, which owned the root widget, was dropped.
+    unsafe {
+        root.destroy();
+    }
+    Ok(Exit::new(model, value))
 }
 
 /// Emit the `msg` every `duration` ms.
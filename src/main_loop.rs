@@ -0,0 +1,109 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! The main loops started by `relm::run()` and the value they quit with.
+
+use std::any::Any;
+use std::cell::RefCell;
+
+use gtk::gio;
+use gtk::prelude::ApplicationExt;
+
+struct RunningLoop {
+    // Whether gtk::main_quit() was already called for this loop.
+    quitting: bool,
+    value: Option<Box<dyn Any>>,
+}
+
+thread_local! {
+    /// The main loops started by `run()`: the last one is the innermost.
+    static MAIN_LOOPS: RefCell<Vec<RunningLoop>> = const { RefCell::new(vec![]) };
+}
+
+/// What the main loop of [`run()`](fn.run.html) returns.
+pub struct Exit<MODEL> {
+    /// The model of the root widget when the main loop quit.
+    /// It is `None` when the widget does not give its model (the `#[widget]` attribute does) or
+    /// when it is still used elsewhere.
+    pub model: Option<MODEL>,
+    value: Option<Box<dyn Any>>,
+}
+
+impl<MODEL> Exit<MODEL> {
+    pub(crate) fn new(model: Option<MODEL>, value: Option<Box<dyn Any>>) -> Self {
+        Exit {
+            model,
+            value,
+        }
+    }
+
+    /// Take the value given to [`quit()`](fn.quit.html), if it has the type `VALUE`.
+    pub fn value<VALUE: 'static>(&mut self) -> Option<VALUE> {
+        match self.value.take()?.downcast::<VALUE>() {
+            Ok(value) => Some(*value),
+            Err(value) => {
+                self.value = Some(value);
+                None
+            },
+        }
+    }
+}
+
+/// Quit the innermost main loop started by [`run()`](fn.run.html), which returns `value` in its
+/// [`Exit`](struct.Exit.html).
+/// The main loops of GTK, like the one of `gtk::Dialog::run()`, are not affected.
+///
+/// Without such a main loop, the default `gio::Application`, like the one of
+/// [`run_application()`](fn.run_application.html), quits instead and `value` is dropped.
+pub fn quit<VALUE: 'static>(value: VALUE) {
+    // None without a main loop, otherwise whether it was already quitting.
+    let quitting = MAIN_LOOPS.with(|main_loops| {
+        main_loops.borrow_mut().last_mut().map(|running_loop| {
+            running_loop.value = Some(Box::new(value));
+            std::mem::replace(&mut running_loop.quitting, true)
+        })
+    });
+    match quitting {
+        // Calling gtk::main_quit() twice would also quit the outer loop.
+        Some(true) => (),
+        Some(false) => gtk::main_quit(),
+        None =>
+            if let Some(application) = gio::Application::default() {
+                application.quit();
+            }
+            else {
+                log::warn!("relm::quit() was called without a main loop started by relm::run() or an application");
+            },
+    }
+}
+
+/// Run a new main loop until `quit()` or `gtk::main_quit()` is called and return the value given
+/// to `quit()`.
+pub(crate) fn run_main_loop() -> Option<Box<dyn Any>> {
+    MAIN_LOOPS.with(|main_loops| main_loops.borrow_mut().push(RunningLoop {
+        quitting: false,
+        value: None,
+    }));
+    // The loop of gtk::main(), so that the applications calling gtk::main_quit() still quit.
+    gtk::main();
+    MAIN_LOOPS.with(|main_loops| main_loops.borrow_mut().pop())
+        .and_then(|running_loop| running_loop.value)
+}
@@ -0,0 +1,208 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Save the geometry of the windows and the position of the panes to a state file, to restore
+//! them when the application is started again.
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+use glib::{Cast, IsA, KeyFile, KeyFileFlags, SourceId};
+use gtk::gdk;
+use gtk::prelude::{GtkWindowExt, PanedExt, WidgetExt};
+use log::warn;
+
+// The state is saved after this delay, to avoid writing the file for every step of a resize.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+thread_local! {
+    /// The state file of the application, loaded when a widget is first persisted.
+    static STATE_FILE: RefCell<Option<Rc<StateFile>>> = const { RefCell::new(None) };
+}
+
+struct StateFile {
+    file: KeyFile,
+    path: PathBuf,
+    save_source: RefCell<Option<SourceId>>,
+}
+
+impl StateFile {
+    fn load(path: PathBuf) -> Self {
+        let file = KeyFile::new();
+        if let Err(error) = file.load_from_file(&path, KeyFileFlags::NONE) {
+            if error.kind::<glib::FileError>() != Some(glib::FileError::Noent) {
+                warn!("Cannot load the state from {}: {}", path.display(), error);
+            }
+        }
+        StateFile {
+            file,
+            path,
+            save_source: RefCell::new(None),
+        }
+    }
+
+    fn save(&self) {
+        if let Some(source) = self.save_source.borrow_mut().take() {
+            source.remove();
+        }
+        self.write();
+    }
+
+    fn schedule_save(self: &Rc<Self>) {
+        if self.save_source.borrow().is_some() {
+            return;
+        }
+        let state_file = Rc::downgrade(self);
+        let source = glib::timeout_add_local_once(SAVE_DELAY, move || {
+            if let Some(state_file) = state_file.upgrade() {
+                let _ = state_file.save_source.borrow_mut().take();
+                state_file.write();
+            }
+        });
+        *self.save_source.borrow_mut() = Some(source);
+    }
+
+    fn write(&self) {
+        if let Some(directory) = self.path.parent() {
+            if let Err(error) = fs::create_dir_all(directory) {
+                warn!("Cannot create the directory {}: {}", directory.display(), error);
+            }
+        }
+        if let Err(error) = self.file.save_to_file(&self.path) {
+            warn!("Cannot save the state to {}: {}", self.path.display(), error);
+        }
+    }
+}
+
+fn default_path() -> PathBuf {
+    let application = glib::prgname().unwrap_or_else(|| "relm".to_string());
+    glib::user_data_dir().join(application).join("state.ini")
+}
+
+fn current_state_file() -> Rc<StateFile> {
+    STATE_FILE.with(|state_file| {
+        state_file.borrow_mut()
+            .get_or_insert_with(|| Rc::new(StateFile::load(default_path())))
+            .clone()
+    })
+}
+
+/// Use the file at `path` to save the state of the widgets, instead of `state.ini` in the
+/// directory of the application in the user data directory.
+/// This must be called before the widgets are persisted.
+pub fn set_state_file<P: AsRef<Path>>(path: P) {
+    let state_file = Rc::new(StateFile::load(path.as_ref().to_path_buf()));
+    let old_state_file = STATE_FILE.with(|current| current.borrow_mut().replace(state_file));
+    if let Some(old_state_file) = old_state_file {
+        if old_state_file.save_source.borrow().is_some() {
+            old_state_file.save();
+        }
+    }
+}
+
+/// A widget whose state can be saved: a `gtk::Window` or a `gtk::Paned`, or one of their
+/// subclasses.
+/// `KIND` distinguishes the implementation for the windows from the one for the panes.
+#[diagnostic::on_unimplemented(
+    message = "the state of `{Self}` cannot be persisted",
+    label = "neither a gtk::Window nor a gtk::Paned",
+    note = "only the state of a gtk::Window or a gtk::Paned can be saved",
+)]
+pub trait Persist<KIND> {
+    /// Restore the state of the widget saved under `name` and save it when it changes.
+    fn persist_state(&self, name: &str);
+}
+
+#[doc(hidden)]
+pub struct WindowKind;
+
+#[doc(hidden)]
+pub struct PanedKind;
+
+impl<WIDGET: IsA<gtk::Window>> Persist<WindowKind> for WIDGET {
+    fn persist_state(&self, name: &str) {
+        persist_window(self.upcast_ref(), name);
+    }
+}
+
+impl<WIDGET: IsA<gtk::Paned>> Persist<PanedKind> for WIDGET {
+    fn persist_state(&self, name: &str) {
+        persist_paned(self.upcast_ref(), name);
+    }
+}
+
+/// Restore the state of `widget` saved under `name` and save it when it changes and when the
+/// widget is destroyed.
+/// The size and the maximized state of a `gtk::Window` and the position of a `gtk::Paned` are
+/// saved: the other widgets are rejected at compile time.
+/// This is what the `#[persist = "name"]` attribute does in the `view!` macro.
+pub fn persist<KIND, WIDGET: Persist<KIND> + IsA<gtk::Widget>>(widget: &WIDGET, name: &str) {
+    widget.persist_state(name);
+    let _ = widget.connect_destroy(|_| current_state_file().save());
+}
+
+fn persist_window(window: &gtk::Window, name: &str) {
+    let state_file = current_state_file();
+    if let (Ok(width), Ok(height)) = (state_file.file.integer(name, "width"), state_file.file.integer(name, "height")) {
+        window.set_default_size(width, height);
+    }
+    if state_file.file.boolean(name, "maximized").unwrap_or(false) {
+        window.maximize();
+    }
+
+    let group = name.to_string();
+    let _ = window.connect_configure_event(move |window, _| {
+        // Keep the size of the window when it is not maximized, to restore it when it is
+        // unmaximized.
+        if !window.is_maximized() {
+            let (width, height) = window.size();
+            let state_file = current_state_file();
+            state_file.file.set_integer(&group, "width", width);
+            state_file.file.set_integer(&group, "height", height);
+            state_file.schedule_save();
+        }
+        false
+    });
+    let group = name.to_string();
+    let _ = window.connect_window_state_event(move |_, event| {
+        let maximized = event.new_window_state().contains(gdk::WindowState::MAXIMIZED);
+        let state_file = current_state_file();
+        state_file.file.set_boolean(&group, "maximized", maximized);
+        state_file.schedule_save();
+        glib::signal::Inhibit(false)
+    });
+}
+
+fn persist_paned(paned: &gtk::Paned, name: &str) {
+    if let Ok(position) = current_state_file().file.integer(name, "position") {
+        paned.set_position(position);
+    }
+
+    let group = name.to_string();
+    let _ = paned.connect_position_notify(move |paned| {
+        let state_file = current_state_file();
+        state_file.file.set_integer(&group, "position", paned.position());
+        state_file.schedule_save();
+    });
+}
@@ -0,0 +1,229 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Cache of the values set to the properties by the `#[widget]` attribute, to avoid calling a
+//! setter with the value it was last called with.
+//!
+//! A cached value is forgotten when the property is changed by something else than the setter,
+//! like the user typing in a `gtk::Entry`, so that the next value is set even if it is the same
+//! as the last one.
+
+use std::any::Any;
+use std::borrow::Borrow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use glib::{IsA, Object, ObjectExt};
+
+use crate::state::{Relm, Update};
+
+type Values = Rc<RefCell<HashMap<&'static str, Box<dyn Any>>>>;
+
+#[derive(Clone)]
+#[doc(hidden)]
+pub struct PropertyCache {
+    setting: Rc<Cell<bool>>,
+    values: Values,
+}
+
+impl PropertyCache {
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        PropertyCache {
+            setting: Rc::new(Cell::new(false)),
+            values: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    /// Forget the value cached for the property when the widget notifies that it changed,
+    /// unless the notification comes from the cached setter itself.
+    /// The setters which are not the setter of a `GObject` property are not watched.
+    #[doc(hidden)]
+    pub fn watch<WIDGET: IsA<Object>>(&self, widget: &WIDGET, key: &'static str, property: &str) {
+        let property = property.replace('_', "-");
+        if widget.find_property(&property).is_none() {
+            return;
+        }
+        let setting = self.setting.clone();
+        let values = self.values.clone();
+        let _ = widget.connect_notify_local(Some(&property), move |_, _| {
+            if !setting.get() {
+                let _ = values.borrow_mut().remove(key);
+            }
+        });
+    }
+
+    /// Check if a cached setter is running.
+    fn is_setting(&self) -> bool {
+        self.setting.get()
+    }
+
+    /// Call the setter, ignoring the notifications it emits.
+    fn set<F: FnOnce()>(&self, set: F) {
+        let setting = self.setting.replace(true);
+        set();
+        self.setting.set(setting);
+    }
+
+    /// Check if the value is different from the last value set to this property and remember it.
+    fn changed<T: PropertyValue>(&self, key: &'static str, value: &T) -> bool {
+        let mut values = self.values.borrow_mut();
+        if let Some(old_value) = values.get(key).and_then(|old_value| old_value.downcast_ref::<T::Owned>()) {
+            if value.eq_owned(old_value) {
+                return false;
+            }
+        }
+        values.insert(key, Box::new(value.to_owned_value()));
+        true
+    }
+
+    /// Remember the value of a property changed from the widget, so that it is not set back.
+    #[doc(hidden)]
+    pub fn store<T: PropertyValue>(&self, key: &'static str, value: T) {
+        let _ = self.changed(key, &value);
+    }
+}
+
+/// Copy the value of a property bound with `bind` into the model, when the widget changes it.
+/// The changes made by the setters of the view are ignored.
+#[doc(hidden)]
+pub fn update_binding<UPDATE, CALLBACK>(relm: &Relm<UPDATE>, cache: &PropertyCache, callback: CALLBACK)
+    where UPDATE: Update,
+          CALLBACK: FnOnce(&mut UPDATE) + 'static,
+{
+    if !cache.is_setting() {
+        relm.with_component(callback);
+    }
+}
+
+/// A value that can be compared with a copy of a value previously set to a property.
+#[doc(hidden)]
+pub trait PropertyValue {
+    #[doc(hidden)]
+    type Owned: Any;
+
+    #[doc(hidden)]
+    fn eq_owned(&self, other: &Self::Owned) -> bool;
+
+    #[doc(hidden)]
+    fn to_owned_value(&self) -> Self::Owned;
+}
+
+impl<T: ?Sized + PartialEq + ToOwned> PropertyValue for &T
+where T::Owned: Any,
+{
+    type Owned = T::Owned;
+
+    fn eq_owned(&self, other: &Self::Owned) -> bool {
+        **self == *other.borrow()
+    }
+
+    fn to_owned_value(&self) -> Self::Owned {
+        (**self).to_owned()
+    }
+}
+
+impl<T: PropertyValue> PropertyValue for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn eq_owned(&self, other: &Self::Owned) -> bool {
+        match (self, other) {
+            (Some(value), Some(other)) => value.eq_owned(other),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn to_owned_value(&self) -> Self::Owned {
+        self.as_ref().map(PropertyValue::to_owned_value)
+    }
+}
+
+macro_rules! impl_property_value {
+    ($($typ:ty),*) => {
+        $(
+            impl PropertyValue for $typ {
+                type Owned = $typ;
+
+                fn eq_owned(&self, other: &Self::Owned) -> bool {
+                    self == other
+                }
+
+                fn to_owned_value(&self) -> Self::Owned {
+                    self.clone()
+                }
+            }
+        )*
+    };
+}
+
+impl_property_value!(bool, char, f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, String);
+
+/// Call to a setter that is skipped when the value did not change.
+///
+/// The setter is wrapped in a closure so that the type of the value is inferred from the
+/// parameter of the setter.
+/// The value is only cached when it implements `PropertyValue`: the methods of the traits
+/// `CachedPropertySetter` and `UncachedPropertySetter` are selected by auto-ref.
+#[doc(hidden)]
+pub struct PropertySetter<F, T> {
+    set: F,
+    _phantom: PhantomData<fn(T)>,
+}
+
+impl<F: Fn(T), T> PropertySetter<F, T> {
+    #[doc(hidden)]
+    pub fn new(set: F) -> Self {
+        PropertySetter {
+            set,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait CachedPropertySetter<T> {
+    #[doc(hidden)]
+    fn set_if_changed(&self, cache: &PropertyCache, key: &'static str, value: T);
+}
+
+impl<F: Fn(T), T: PropertyValue> CachedPropertySetter<T> for PropertySetter<F, T> {
+    fn set_if_changed(&self, cache: &PropertyCache, key: &'static str, value: T) {
+        if cache.changed(key, &value) {
+            cache.set(|| (self.set)(value));
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait UncachedPropertySetter<T> {
+    #[doc(hidden)]
+    fn set_if_changed(&self, cache: &PropertyCache, key: &'static str, value: T);
+}
+
+impl<F: Fn(T), T> UncachedPropertySetter<T> for &PropertySetter<F, T> {
+    fn set_if_changed(&self, cache: &PropertyCache, _key: &'static str, value: T) {
+        cache.set(|| (self.set)(value));
+    }
+}
+
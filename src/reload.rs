@@ -0,0 +1,762 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Reload the literal values of the properties of a view written in a `.relm` file when the file
+//! changes, in debug builds and when the `RELM_RELOAD` environment variable is set.
+//!
+//! The widget tree is not rebuilt: the new values of the properties and of the child properties
+//! are set to the existing widgets, so that the model is preserved. The other changes, like adding
+//! a widget, need new Rust code, so they are only reported with a warning.
+
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::env;
+use std::fs;
+
+use glib::{Cast, Object, ObjectExt, ParamFlags, ParamSpec, ToValue, Type, Value, WeakRef};
+use glib::translate::{from_glib_none, ToGlibPtr};
+use gtk::gio;
+use gtk::gio::prelude::{FileExt, FileMonitorExt};
+use gtk::prelude::WidgetExt;
+
+/// Environment variable enabling the reload of the view files.
+const RELOAD_VAR: &str = "RELM_RELOAD";
+
+/// The setters of the view which are not named after their property.
+const PROPERTY_ALIASES: &[(&str, &str, &str)] = &[
+    ("GtkLabel", "text", "label"),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Group(char, Vec<Token>),
+    Ident(String),
+    Literal(String),
+    Punct(char),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Item {
+    ChildProperty(String, Vec<Token>),
+    Other(Vec<Token>),
+    Property(String, Vec<Token>),
+}
+
+/// A widget of the view, in the order the `#[widget]` attribute gives the widgets to
+/// `watch_view_file()`.
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    children: Vec<Node>,
+    items: Vec<Item>,
+    path: Vec<Token>,
+}
+
+impl Node {
+    fn count(&self) -> usize {
+        1 + self.children.iter().map(Node::count).sum::<usize>()
+    }
+}
+
+struct Change {
+    child: bool,
+    index: usize,
+    name: String,
+    value: Vec<Token>,
+}
+
+enum Literal {
+    Bool(bool),
+    Enum(String),
+    Float(f64),
+    Integer(i64),
+    Str(String),
+}
+
+/// Watch the file of the view to set the new values of the properties to the `widgets` when it
+/// changes, if the `RELM_RELOAD` environment variable is set.
+/// `source` is the content of the file when the widget was compiled and `widgets` contains every
+/// widget of the view in depth-first order, `None` being used for the widgets that cannot be
+/// reloaded, like the relm widgets.
+#[doc(hidden)]
+pub fn watch_view_file(path: &str, source: &str, widgets: Vec<Option<Object>>) {
+    if env::var_os(RELOAD_VAR).is_none() {
+        return;
+    }
+    let view = match parse_view(source) {
+        Ok(view) => view,
+        Err(error) => {
+            log::warn!("Cannot reload the view {}: {}", path, error);
+            return;
+        },
+    };
+    if view.iter().map(Node::count).sum::<usize>() != widgets.len() {
+        log::warn!("Cannot reload the view {}: the widgets of the file do not match the generated widgets", path);
+        return;
+    }
+    let root = match widgets.iter().flatten().find_map(|object| object.downcast_ref::<gtk::Widget>()) {
+        Some(root) => root.clone(),
+        None => return,
+    };
+    let monitor = match gio::File::for_path(path).monitor_file(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE) {
+        Ok(monitor) => monitor,
+        Err(error) => {
+            log::warn!("Cannot watch the view {}: {}", path, error);
+            return;
+        },
+    };
+    let widgets: Vec<Option<WeakRef<Object>>> = widgets.iter()
+        .map(|object| object.as_ref().map(ObjectExt::downgrade))
+        .collect();
+    let current_view = RefCell::new(view);
+    let path = path.to_string();
+    monitor.connect_changed(move |_, _, _, event| {
+        if event == gio::FileMonitorEvent::ChangesDoneHint || event == gio::FileMonitorEvent::Created {
+            reload(&path, &current_view, &widgets);
+        }
+    });
+    // The monitor lives as long as the widgets.
+    root.connect_destroy(move |_| {
+        let _ = monitor.cancel();
+    });
+}
+
+fn reload(path: &str, current_view: &RefCell<Vec<Node>>, widgets: &[Option<WeakRef<Object>>]) {
+    let view = fs::read_to_string(path)
+        .map_err(|error| error.to_string())
+        .and_then(|source| parse_view(&source));
+    let view = match view {
+        Ok(view) => view,
+        Err(error) => {
+            log::warn!("Cannot reload the view {}: {}", path, error);
+            return;
+        },
+    };
+    let mut changes = vec![];
+    let mut index = 0;
+    let result =
+        if view.len() == current_view.borrow().len() {
+            current_view.borrow().iter().zip(&view)
+                .try_for_each(|(old, new)| diff(old, new, &mut index, &mut changes))
+        }
+        else {
+            Err("widgets were added or removed".to_string())
+        };
+    if let Err(error) = result {
+        log::warn!("The view {} needs to be recompiled: {}", path, error);
+        return;
+    }
+    for change in changes {
+        if let Err(error) = apply(&change, widgets) {
+            log::warn!("Cannot reload the property {} in the view {}: {}", change.name, path, error);
+        }
+    }
+    *current_view.borrow_mut() = view;
+}
+
+/// Get the properties which changed, only allowing a new value for the properties that had a
+/// literal value.
+fn diff(old: &Node, new: &Node, index: &mut usize, changes: &mut Vec<Change>) -> Result<(), String> {
+    let widget_index = *index;
+    *index += 1;
+    let path =
+        match old.path.first() {
+            Some(Token::Ident(keyword)) if keyword == "if" || keyword == "match" || keyword == "for" =>
+                format!("the `{}` block", keyword),
+            _ => tokens_to_string(&old.path),
+        };
+    if old.path != new.path {
+        return Err(format!("the code of {} changed", path));
+    }
+    if old.items.len() != new.items.len() || old.children.len() != new.children.len() {
+        return Err(format!("items were added to or removed from {}", path));
+    }
+    for (old_item, new_item) in old.items.iter().zip(&new.items) {
+        match (old_item, new_item) {
+            (Item::Property(old_name, old_value), Item::Property(new_name, new_value)) |
+            (Item::ChildProperty(old_name, old_value), Item::ChildProperty(new_name, new_value))
+                if old_name == new_name =>
+            {
+                if old_value != new_value {
+                    if parse_literal(old_value).is_none() || parse_literal(new_value).is_none() {
+                        return Err(format!("the value of {} in {} is not a literal", old_name, path));
+                    }
+                    changes.push(Change {
+                        child: matches!(new_item, Item::ChildProperty(_, _)),
+                        index: widget_index,
+                        name: new_name.clone(),
+                        value: new_value.clone(),
+                    });
+                }
+            },
+            (Item::Other(old_tokens), Item::Other(new_tokens)) if old_tokens == new_tokens => (),
+            _ => return Err(format!("the code of {} changed", path)),
+        }
+    }
+    for (old_child, new_child) in old.children.iter().zip(&new.children) {
+        diff(old_child, new_child, index, changes)?;
+    }
+    Ok(())
+}
+
+fn apply(change: &Change, widgets: &[Option<WeakRef<Object>>]) -> Result<(), String> {
+    let object = widgets.get(change.index)
+        .and_then(|widget| widget.as_ref())
+        .and_then(WeakRef::upgrade)
+        .ok_or_else(|| "only the properties of the GTK widgets can be reloaded".to_string())?;
+    let literal = parse_literal(&change.value).ok_or_else(|| "the value is not a literal".to_string())?;
+    if change.child {
+        let widget = object.downcast::<gtk::Widget>()
+            .map_err(|_| "the object is not a widget".to_string())?;
+        let container = widget.parent()
+            .and_then(|parent| parent.downcast::<gtk::Container>().ok())
+            .ok_or_else(|| "the widget is not in a container".to_string())?;
+        let pspec = find_child_property(&container, &change.name.replace('_', "-"))
+            .ok_or_else(|| format!("no child property {} in {}", change.name, container.type_()))?;
+        let value = to_value(&pspec, &literal)?;
+        unsafe {
+            gtk::ffi::gtk_container_child_set_property(container.to_glib_none().0, widget.to_glib_none().0,
+                pspec.name().to_glib_none().0, value.to_glib_none().0);
+        }
+    }
+    else {
+        let type_name = object.type_().name();
+        let name = PROPERTY_ALIASES.iter()
+            .find(|&&(class, setter, _)| type_name == class && setter == change.name)
+            .map(|&(_, _, property)| property.to_string())
+            .unwrap_or_else(|| change.name.replace('_', "-"));
+        let pspec = object.find_property(&name)
+            .ok_or_else(|| format!("{} is not a property of {}", change.name, type_name))?;
+        let value = to_value(&pspec, &literal)?;
+        object.set_property_from_value(pspec.name(), &value);
+    }
+    Ok(())
+}
+
+fn find_child_property(container: &gtk::Container, name: &str) -> Option<ParamSpec> {
+    let class = glib::Class::<gtk::Container>::from_type(container.type_())?;
+    let class: *const gtk::ffi::GtkContainerClass = class.as_ref();
+    unsafe {
+        from_glib_none(gtk::ffi::gtk_container_class_find_child_property(class.cast(), name.to_glib_none().0))
+    }
+}
+
+/// Convert the literal to a value of the type of the property.
+fn to_value(pspec: &ParamSpec, literal: &Literal) -> Result<Value, String> {
+    if !pspec.flags().contains(ParamFlags::WRITABLE) || pspec.flags().contains(ParamFlags::CONSTRUCT_ONLY) {
+        return Err("the property cannot be set after the widget is created".to_string());
+    }
+    let typ = pspec.value_type();
+    let value =
+        match *literal {
+            Literal::Bool(value) if typ == Type::BOOL => Some(value.to_value()),
+            Literal::Str(ref value) if typ == Type::STRING => Some(value.to_value()),
+            Literal::Integer(value) if typ == Type::I8 => i8::try_from(value).ok().map(|value| value.to_value()),
+            Literal::Integer(value) if typ == Type::U8 => u8::try_from(value).ok().map(|value| value.to_value()),
+            Literal::Integer(value) if typ == Type::I32 => i32::try_from(value).ok().map(|value| value.to_value()),
+            Literal::Integer(value) if typ == Type::U32 => u32::try_from(value).ok().map(|value| value.to_value()),
+            Literal::Integer(value) if typ == Type::I64 => Some(value.to_value()),
+            Literal::Integer(value) if typ == Type::U64 => u64::try_from(value).ok().map(|value| value.to_value()),
+            Literal::Integer(value) if typ == Type::F32 => Some((value as f32).to_value()),
+            Literal::Integer(value) if typ == Type::F64 => Some((value as f64).to_value()),
+            Literal::Float(value) if typ == Type::F32 => Some((value as f32).to_value()),
+            Literal::Float(value) if typ == Type::F64 => Some(value.to_value()),
+            Literal::Enum(ref variant) if typ.is_a(Type::ENUM) =>
+                glib::EnumClass::new(typ).and_then(|class| class.to_value_by_nick(&enum_nick(variant))),
+            _ => None,
+        };
+    value.ok_or_else(|| format!("the value cannot be converted to {}", typ))
+}
+
+/// Convert a variant name like `SpaceBetween` to its GObject nick, like `space-between`.
+fn enum_nick(variant: &str) -> String {
+    let mut nick = String::new();
+    for (index, character) in variant.chars().enumerate() {
+        if character.is_uppercase() && index > 0 {
+            nick.push('-');
+        }
+        nick.extend(character.to_lowercase());
+    }
+    nick
+}
+
+fn parse_literal(tokens: &[Token]) -> Option<Literal> {
+    match tokens {
+        [Token::Ident(ident)] if ident == "true" || ident == "false" => Some(Literal::Bool(ident == "true")),
+        [Token::Literal(literal)] => parse_literal_token(literal),
+        [Token::Punct('-'), Token::Literal(literal)] =>
+            match parse_literal_token(literal)? {
+                Literal::Float(value) => Some(Literal::Float(-value)),
+                Literal::Integer(value) => Some(Literal::Integer(-value)),
+                _ => None,
+            },
+        // An enum variant, like `Vertical` or `gtk::Orientation::Vertical`.
+        [.., Token::Ident(variant)] if variant.starts_with(char::is_uppercase) => {
+            let is_path = tokens.iter().all(|token| matches!(token, Token::Ident(_) | Token::Punct(':')));
+            if is_path {
+                Some(Literal::Enum(variant.clone()))
+            }
+            else {
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+fn parse_literal_token(literal: &str) -> Option<Literal> {
+    if literal.starts_with('"') {
+        return unescape(&literal[1..literal.len() - 1]).map(Literal::Str);
+    }
+    if let Some(raw) = literal.strip_prefix('r') {
+        let raw = raw.trim_matches('#');
+        return Some(Literal::Str(raw[1..raw.len() - 1].to_string()));
+    }
+    let number = literal.replace('_', "");
+    let (radix, digits) =
+        if let Some(digits) = number.strip_prefix("0x") {
+            (16, digits)
+        }
+        else if let Some(digits) = number.strip_prefix("0o") {
+            (8, digits)
+        }
+        else if let Some(digits) = number.strip_prefix("0b") {
+            (2, digits)
+        }
+        else {
+            (10, number.as_str())
+        };
+    if radix == 10 && (digits.contains('.') || digits.contains('e') || digits.contains('E') || digits.ends_with("f32")
+        || digits.ends_with("f64"))
+    {
+        let digits = digits.trim_end_matches("f32").trim_end_matches("f64");
+        return digits.parse().ok().map(Literal::Float);
+    }
+    let suffix_start = digits.find(['i', 'u']).unwrap_or(digits.len());
+    i64::from_str_radix(&digits[..suffix_start], radix).ok().map(Literal::Integer)
+}
+
+fn unescape(string: &str) -> Option<String> {
+    let mut result = String::new();
+    let mut chars = string.chars().peekable();
+    while let Some(character) = chars.next() {
+        if character != '\\' {
+            result.push(character);
+            continue;
+        }
+        match chars.next()? {
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            '0' => result.push('\0'),
+            '\\' => result.push('\\'),
+            '\'' => result.push('\''),
+            '"' => result.push('"'),
+            'u' => {
+                let code: String = chars.by_ref().skip(1).take_while(|&character| character != '}').collect();
+                result.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            },
+            '\n' => {
+                while chars.peek().is_some_and(|character| character.is_whitespace()) {
+                    let _ = chars.next();
+                }
+            },
+            _ => return None,
+        }
+    }
+    Some(result)
+}
+
+fn tokens_to_string(tokens: &[Token]) -> String {
+    let mut string = String::new();
+    for token in tokens {
+        match *token {
+            Token::Group(delimiter, ref tokens) => {
+                string.push(delimiter);
+                string.push_str(&tokens_to_string(tokens));
+                string.push(closing_delimiter(delimiter).unwrap_or(delimiter));
+            },
+            Token::Ident(ref text) | Token::Literal(ref text) => {
+                if string.ends_with(|character: char| character.is_alphanumeric() || character == '_' || character == '"') {
+                    string.push(' ');
+                }
+                string.push_str(text);
+            },
+            Token::Punct(character) => string.push(character),
+        }
+    }
+    string
+}
+
+fn parse_view(source: &str) -> Result<Vec<Node>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut index = 0;
+    let tokens = tokenize(&chars, &mut index, None)?;
+    let mut index = 0;
+    let mut widgets = vec![];
+    while index < tokens.len() {
+        widgets.push(parse_widget(&tokens, &mut index)?);
+        if tokens.get(index) == Some(&Token::Punct(',')) {
+            index += 1;
+        }
+    }
+    Ok(widgets)
+}
+
+/// Parse a widget: attributes, path, parameters and body.
+fn parse_widget(tokens: &[Token], index: &mut usize) -> Result<Node, String> {
+    let mut items = vec![];
+    while let (Some(Token::Punct('#')), Some(attribute @ Token::Group('[', _))) = (tokens.get(*index), tokens.get(*index + 1)) {
+        items.push(Item::Other(vec![Token::Punct('#'), attribute.clone()]));
+        *index += 2;
+    }
+    if let Some(Token::Ident(keyword)) = tokens.get(*index) {
+        if keyword == "if" || keyword == "match" || keyword == "for" {
+            // The widgets of the conditions and of the loops are created by the generated code.
+            let path = tokens[*index..].to_vec();
+            *index = tokens.len();
+            return Ok(Node {
+                children: vec![],
+                items,
+                path,
+            });
+        }
+    }
+    let start = *index;
+    while let Some(token) = tokens.get(*index) {
+        if matches!(token, Token::Group(_, _)) || *token == Token::Punct(',') {
+            break;
+        }
+        *index += 1;
+    }
+    let path = tokens[start..*index].to_vec();
+    if path.is_empty() {
+        return Err(format!("expected a widget, found {}", tokens_to_string(&tokens[start..])));
+    }
+    if let Some(parameters @ Token::Group('(', _)) = tokens.get(*index) {
+        items.push(Item::Other(vec![parameters.clone()]));
+        *index += 1;
+    }
+    let mut children = vec![];
+    if let Some(Token::Group('{', body)) = tokens.get(*index) {
+        for item in split_items(body) {
+            parse_item(item, &mut items, &mut children);
+        }
+        *index += 1;
+    }
+    Ok(Node {
+        children,
+        items,
+        path,
+    })
+}
+
+/// Parse an item of the body of a widget: a property, child properties, a child widget or
+/// anything else, like an event.
+fn parse_item(tokens: &[Token], items: &mut Vec<Item>, children: &mut Vec<Node>) {
+    let is_event = tokens.windows(2).any(|tokens| tokens == [Token::Punct('='), Token::Punct('>')]);
+    if let [Token::Ident(name), Token::Punct(':'), value @ ..] = tokens {
+        if !is_event && value.first() != Some(&Token::Punct(':')) {
+            match value {
+                [Token::Group('{', properties)] if name == "child" => {
+                    for property in split_items(properties) {
+                        match property {
+                            [Token::Ident(key), Token::Punct(':'), value @ ..] =>
+                                items.push(Item::ChildProperty(key.clone(), value.to_vec())),
+                            _ => items.push(Item::Other(property.to_vec())),
+                        }
+                    }
+                },
+                [Token::Group('{', _)] => items.push(Item::Other(tokens.to_vec())),
+                _ => items.push(Item::Property(name.clone(), value.to_vec())),
+            }
+            return;
+        }
+    }
+    if !is_event {
+        let mut index = 0;
+        if let Ok(widget) = parse_widget(tokens, &mut index) {
+            if index == tokens.len() {
+                children.push(widget);
+                return;
+            }
+        }
+    }
+    items.push(Item::Other(tokens.to_vec()));
+}
+
+/// Split the tokens at the commas, except those in the parameters of closures.
+fn split_items(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut items = vec![];
+    let mut start = 0;
+    let mut in_closure_parameters = false;
+    for (index, token) in tokens.iter().enumerate() {
+        match *token {
+            Token::Punct('|') => in_closure_parameters = !in_closure_parameters,
+            Token::Punct(',') if !in_closure_parameters => {
+                items.push(&tokens[start..index]);
+                start = index + 1;
+            },
+            _ => (),
+        }
+    }
+    if start < tokens.len() {
+        items.push(&tokens[start..]);
+    }
+    items
+}
+
+fn closing_delimiter(delimiter: char) -> Option<char> {
+    match delimiter {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+fn starts_with(chars: &[char], index: usize, prefix: &str) -> bool {
+    prefix.chars().enumerate().all(|(offset, character)| chars.get(index + offset) == Some(&character))
+}
+
+fn tokenize(chars: &[char], index: &mut usize, close: Option<char>) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    while let Some(&character) = chars.get(*index) {
+        if character.is_whitespace() {
+            *index += 1;
+        }
+        else if starts_with(chars, *index, "//") {
+            while chars.get(*index).is_some_and(|&character| character != '\n') {
+                *index += 1;
+            }
+        }
+        else if starts_with(chars, *index, "/*") {
+            let mut depth = 0;
+            loop {
+                if starts_with(chars, *index, "/*") {
+                    depth += 1;
+                    *index += 2;
+                }
+                else if starts_with(chars, *index, "*/") {
+                    depth -= 1;
+                    *index += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                else if *index < chars.len() {
+                    *index += 1;
+                }
+                else {
+                    return Err("unterminated comment".to_string());
+                }
+            }
+        }
+        else if let Some(closing) = closing_delimiter(character) {
+            *index += 1;
+            let group = tokenize(chars, index, Some(closing))?;
+            tokens.push(Token::Group(character, group));
+        }
+        else if character == ')' || character == ']' || character == '}' {
+            if close == Some(character) {
+                *index += 1;
+                return Ok(tokens);
+            }
+            return Err(format!("unexpected `{}`", character));
+        }
+        else if character == '"' || starts_with(chars, *index, "r\"") || starts_with(chars, *index, "r#\"")
+            || starts_with(chars, *index, "r##") {
+            tokens.push(Token::Literal(string_literal(chars, index)?));
+        }
+        else if character == '\'' && (chars.get(*index + 1) == Some(&'\\') || chars.get(*index + 2) == Some(&'\'')) {
+            let start = *index;
+            *index += 1;
+            while chars.get(*index).is_some_and(|&character| character != '\'') {
+                *index += if chars[*index] == '\\' { 2 } else { 1 };
+            }
+            *index += 1;
+            tokens.push(Token::Literal(chars[start..(*index).min(chars.len())].iter().collect()));
+        }
+        else if character.is_alphabetic() || character == '_' {
+            let start = *index;
+            while chars.get(*index).is_some_and(|&character| character.is_alphanumeric() || character == '_') {
+                *index += 1;
+            }
+            tokens.push(Token::Ident(chars[start..*index].iter().collect()));
+        }
+        else if character.is_ascii_digit() {
+            let start = *index;
+            let is_hexadecimal = starts_with(chars, *index, "0x");
+            while let Some(&character) = chars.get(*index) {
+                let is_exponent_sign = (character == '+' || character == '-') && !is_hexadecimal && *index > start
+                    && (chars[*index - 1] == 'e' || chars[*index - 1] == 'E');
+                let is_decimal_point = character == '.' && chars.get(*index + 1).is_some_and(char::is_ascii_digit);
+                if character.is_alphanumeric() || character == '_' || is_exponent_sign || is_decimal_point {
+                    *index += 1;
+                }
+                else {
+                    break;
+                }
+            }
+            tokens.push(Token::Literal(chars[start..*index].iter().collect()));
+        }
+        else {
+            tokens.push(Token::Punct(character));
+            *index += 1;
+        }
+    }
+    match close {
+        Some(close) => Err(format!("unclosed delimiter, expected `{}`", close)),
+        None => Ok(tokens),
+    }
+}
+
+fn string_literal(chars: &[char], index: &mut usize) -> Result<String, String> {
+    let start = *index;
+    if chars[*index] == 'r' {
+        *index += 1;
+        let mut hashes = 0;
+        while chars.get(*index) == Some(&'#') {
+            hashes += 1;
+            *index += 1;
+        }
+        let end: String = Some('"').into_iter().chain(std::iter::repeat_n('#', hashes)).collect();
+        *index += 1;
+        while !starts_with(chars, *index, &end) {
+            if *index >= chars.len() {
+                return Err("unterminated string".to_string());
+            }
+            *index += 1;
+        }
+        *index += end.len();
+    }
+    else {
+        *index += 1;
+        loop {
+            match chars.get(*index) {
+                Some('"') => break,
+                Some('\\') => *index += 2,
+                Some(_) => *index += 1,
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+        *index += 1;
+    }
+    Ok(chars[start..*index].iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Item, Node, Token, diff, parse_view};
+
+    const VIEW: &str = r#"
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                // The button.
+                #[name="button"]
+                gtk::Button {
+                    clicked => Increment,
+                    label: "+",
+                    child: {
+                        padding: 5,
+                    },
+                },
+            },
+        }
+    "#;
+
+    fn ident(ident: &str) -> Token {
+        Token::Ident(ident.to_string())
+    }
+
+    fn gtk_path(name: &str) -> Vec<Token> {
+        vec![ident("gtk"), Token::Punct(':'), Token::Punct(':'), ident(name)]
+    }
+
+    fn changes(old: &str, new: &str) -> Result<Vec<(usize, String, Vec<Token>)>, String> {
+        let old = parse_view(old)?;
+        let new = parse_view(new)?;
+        let mut changes = vec![];
+        let mut index = 0;
+        for (old, new) in old.iter().zip(&new) {
+            diff(old, new, &mut index, &mut changes)?;
+        }
+        Ok(changes.into_iter().map(|change| (change.index, change.name, change.value)).collect())
+    }
+
+    #[test]
+    fn parse() {
+        let view = parse_view(VIEW).expect("parse_view");
+        let button = Node {
+            children: vec![],
+            items: vec![
+                Item::Other(vec![Token::Punct('#'), Token::Group('[', vec![ident("name"), Token::Punct('='),
+                    Token::Literal("\"button\"".to_string())])]),
+                Item::Other(vec![ident("clicked"), Token::Punct('='), Token::Punct('>'), ident("Increment")]),
+                Item::Property("label".to_string(), vec![Token::Literal("\"+\"".to_string())]),
+                Item::ChildProperty("padding".to_string(), vec![Token::Literal("5".to_string())]),
+            ],
+            path: gtk_path("Button"),
+        };
+        let window = Node {
+            children: vec![Node {
+                children: vec![button],
+                items: vec![Item::Property("orientation".to_string(), vec![ident("Vertical")])],
+                path: gtk_path("Box"),
+            }],
+            items: vec![],
+            path: gtk_path("Window"),
+        };
+        assert_eq!(view, vec![window]);
+
+        assert!(parse_view("gtk::Window { gtk::Box {").is_err());
+        assert!(parse_view("gtk::Window { label: \"unterminated }").is_err());
+    }
+
+    #[test]
+    fn property_changes() {
+        let new = VIEW.replace("Vertical", "Horizontal").replace("\"+\"", "\"Increment\"").replace("5", "10");
+        assert_eq!(changes(VIEW, &new), Ok(vec![
+            (1, "orientation".to_string(), vec![ident("Horizontal")]),
+            (2, "label".to_string(), vec![Token::Literal("\"Increment\"".to_string())]),
+            (2, "padding".to_string(), vec![Token::Literal("10".to_string())]),
+        ]));
+        assert_eq!(changes(VIEW, &VIEW.replace("// The button.", "")), Ok(vec![]));
+    }
+
+    #[test]
+    fn structural_changes() {
+        let added_widget = VIEW.replace("gtk::Button {", "gtk::Label { }, gtk::Button {");
+        assert!(changes(VIEW, &added_widget).is_err());
+
+        let added_property = VIEW.replace("label: \"+\",", "label: \"+\", sensitive: false,");
+        assert!(changes(VIEW, &added_property).is_err());
+
+        let changed_event = VIEW.replace("Increment", "Decrement");
+        assert!(changes(VIEW, &changed_event).is_err());
+
+        let changed_widget = VIEW.replace("gtk::Box", "gtk::Grid");
+        assert!(changes(VIEW, &changed_widget).is_err());
+
+        let expression = VIEW.replace("\"+\"", "&self.model.label");
+        assert!(changes(VIEW, &expression).is_err());
+    }
+}
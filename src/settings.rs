@@ -0,0 +1,354 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Persistent settings, stored with `gio::Settings`, in a key file or in memory.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+
+use glib::{FromVariant, KeyFile, KeyFileFlags, ToVariant, Variant};
+use gtk::gio;
+use gtk::gio::prelude::{FileExt, FileMonitorExt, SettingsExt};
+use log::warn;
+
+use crate::StreamHandle;
+
+type Callback = Rc<dyn Fn(&str)>;
+type Watcher = Rc<dyn Fn(&Variant)>;
+
+/// A store where the settings are read and written.
+pub trait SettingsStore {
+    /// Get the value of `key`, if it is set.
+    fn read(&self, key: &str) -> Option<Variant>;
+
+    /// Set the value of `key`.
+    fn write(&self, key: &str, value: &Variant);
+
+    /// Call `callback` with the key whose value changed in the store, including when it is changed
+    /// by another process.
+    fn connect_changed(&self, callback: Box<dyn Fn(&str)>);
+}
+
+impl SettingsStore for gio::Settings {
+    fn read(&self, key: &str) -> Option<Variant> {
+        // Reading a key which is not in the schema aborts the program.
+        if self.settings_schema().map(|schema| schema.has_key(key)).unwrap_or(false) {
+            Some(self.value(key))
+        }
+        else {
+            warn!("The key {} is not in the schema of the settings", key);
+            None
+        }
+    }
+
+    fn write(&self, key: &str, value: &Variant) {
+        if let Err(error) = self.set_value(key, value) {
+            warn!("Cannot write the setting {}: {}", key, error);
+        }
+    }
+
+    fn connect_changed(&self, callback: Box<dyn Fn(&str)>) {
+        let _ = SettingsExt::connect_changed(self, None, move |_, key| callback(key));
+    }
+}
+
+struct KeyFileData {
+    callbacks: RefCell<Vec<Callback>>,
+    file: RefCell<KeyFile>,
+    group: String,
+    monitor: RefCell<Option<gio::FileMonitor>>,
+    path: PathBuf,
+}
+
+impl KeyFileData {
+    // Load the file again and notify the keys which changed.
+    fn reload(&self) {
+        let file = load_key_file(&self.path);
+        let old_file = self.file.replace(file);
+        let keys = |file: &KeyFile| file.keys(&self.group).map(|(keys, _)| keys).unwrap_or_default();
+        let mut keys: Vec<_> = keys(&old_file).into_iter().chain(keys(&self.file.borrow())).collect();
+        keys.sort();
+        keys.dedup();
+        let callbacks = self.callbacks.borrow().clone();
+        for key in keys {
+            if old_file.string(&self.group, &key).ok() != self.file.borrow().string(&self.group, &key).ok() {
+                for callback in &callbacks {
+                    callback(&key);
+                }
+            }
+        }
+    }
+}
+
+/// A store saving the settings in a group of a key file, for the applications without an
+/// installed `gio::Settings` schema.
+/// The values are saved in the GVariant text format and the file is watched for external changes.
+#[derive(Clone)]
+pub struct KeyFileStore {
+    data: Rc<KeyFileData>,
+}
+
+impl KeyFileStore {
+    /// Create a store for the `group` of the key file at `path`, which is created when a setting
+    /// is written.
+    pub fn new<P: AsRef<Path>>(path: P, group: &str) -> Self {
+        let path = path.as_ref().to_path_buf();
+        KeyFileStore {
+            data: Rc::new(KeyFileData {
+                callbacks: RefCell::new(vec![]),
+                file: RefCell::new(load_key_file(&path)),
+                group: group.to_string(),
+                monitor: RefCell::new(None),
+                path,
+            }),
+        }
+    }
+
+    /// Get the path of the key file.
+    pub fn path(&self) -> &Path {
+        &self.data.path
+    }
+
+    fn watch(&self) {
+        if self.data.monitor.borrow().is_some() {
+            return;
+        }
+        let file = gio::File::for_path(&self.data.path);
+        match file.monitor_file(gio::FileMonitorFlags::NONE, None::<&gio::Cancellable>) {
+            Ok(monitor) => {
+                let data = Rc::downgrade(&self.data);
+                let _ = monitor.connect_changed(move |_, _, _, event| {
+                    match event {
+                        gio::FileMonitorEvent::ChangesDoneHint | gio::FileMonitorEvent::Created |
+                            gio::FileMonitorEvent::Deleted =>
+                        {
+                            if let Some(data) = Weak::upgrade(&data) {
+                                data.reload();
+                            }
+                        },
+                        _ => (),
+                    }
+                });
+                *self.data.monitor.borrow_mut() = Some(monitor);
+            },
+            Err(error) => warn!("Cannot watch the settings file {}: {}", self.data.path.display(), error),
+        }
+    }
+}
+
+impl SettingsStore for KeyFileStore {
+    fn read(&self, key: &str) -> Option<Variant> {
+        let text = self.data.file.borrow().string(&self.data.group, key).ok()?;
+        match Variant::parse(None, &text) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                warn!("Cannot parse the setting {} in {}: {}", key, self.data.path.display(), error);
+                None
+            },
+        }
+    }
+
+    fn write(&self, key: &str, value: &Variant) {
+        let file = self.data.file.borrow();
+        file.set_string(&self.data.group, key, &value.print(true));
+        if let Some(directory) = self.data.path.parent() {
+            if let Err(error) = fs::create_dir_all(directory) {
+                warn!("Cannot create the directory {}: {}", directory.display(), error);
+            }
+        }
+        if let Err(error) = file.save_to_file(&self.data.path) {
+            warn!("Cannot save the settings to {}: {}", self.data.path.display(), error);
+        }
+    }
+
+    fn connect_changed(&self, callback: Box<dyn Fn(&str)>) {
+        self.data.callbacks.borrow_mut().push(Rc::from(callback));
+        self.watch();
+    }
+}
+
+fn load_key_file(path: &Path) -> KeyFile {
+    let file = KeyFile::new();
+    if let Err(error) = file.load_from_file(path, KeyFileFlags::KEEP_COMMENTS) {
+        if error.kind::<glib::FileError>() != Some(glib::FileError::Noent) {
+            warn!("Cannot load the settings from {}: {}", path.display(), error);
+        }
+    }
+    file
+}
+
+#[derive(Default)]
+struct MemoryData {
+    callbacks: RefCell<Vec<Callback>>,
+    values: RefCell<HashMap<String, Variant>>,
+}
+
+/// A store keeping the settings in memory, which is useful in tests.
+/// Its clones share the same values, so writing to a clone simulates an external change.
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    data: Rc<MemoryData>,
+}
+
+impl MemoryStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the value of `key`.
+    pub fn set<T: ToVariant>(&self, key: &str, value: &T) {
+        self.write(key, &value.to_variant());
+    }
+}
+
+impl SettingsStore for MemoryStore {
+    fn read(&self, key: &str) -> Option<Variant> {
+        self.data.values.borrow().get(key).cloned()
+    }
+
+    fn write(&self, key: &str, value: &Variant) {
+        let _ = self.data.values.borrow_mut().insert(key.to_string(), value.clone());
+        let callbacks = self.data.callbacks.borrow().clone();
+        for callback in callbacks {
+            callback(key);
+        }
+    }
+
+    fn connect_changed(&self, callback: Box<dyn Fn(&str)>) {
+        self.data.callbacks.borrow_mut().push(Rc::from(callback));
+    }
+}
+
+struct SettingsData {
+    store: Box<dyn SettingsStore>,
+    // The last values read or written, to only notify the changes made outside of this object.
+    values: RefCell<HashMap<String, Variant>>,
+    watchers: RefCell<HashMap<String, Vec<Watcher>>>,
+}
+
+impl SettingsData {
+    fn changed(&self, key: &str) {
+        let value = self.store.read(key);
+        {
+            let mut values = self.values.borrow_mut();
+            if values.get(key) == value.as_ref() {
+                return;
+            }
+            match value {
+                Some(ref value) => values.insert(key.to_string(), value.clone()),
+                None => values.remove(key),
+            };
+        }
+        if let Some(value) = value {
+            let watchers = self.watchers.borrow().get(key).cloned().unwrap_or_default();
+            for watcher in watchers {
+                watcher(&value);
+            }
+        }
+    }
+}
+
+/// Typed access to the settings of a store, which can be bound to the model with the `settings!`
+/// macro.
+/// The clones share the same store.
+#[derive(Clone)]
+pub struct Settings {
+    data: Rc<SettingsData>,
+}
+
+impl Settings {
+    /// Create settings read from and written to `store`.
+    pub fn new<STORE: SettingsStore + 'static>(store: STORE) -> Self {
+        let data = Rc::new(SettingsData {
+            store: Box::new(store),
+            values: RefCell::new(HashMap::new()),
+            watchers: RefCell::new(HashMap::new()),
+        });
+        let weak_data = Rc::downgrade(&data);
+        data.store.connect_changed(Box::new(move |key| {
+            if let Some(data) = weak_data.upgrade() {
+                data.changed(key);
+            }
+        }));
+        Settings {
+            data,
+        }
+    }
+
+    /// Create settings stored with `gio::Settings`, whose schema `schema_id` must be installed.
+    pub fn gsettings(schema_id: &str) -> Self {
+        Self::new(gio::Settings::new(schema_id))
+    }
+
+    /// Create settings stored in the `group` of the key file at `path`.
+    pub fn key_file<P: AsRef<Path>>(path: P, group: &str) -> Self {
+        Self::new(KeyFileStore::new(path, group))
+    }
+
+    /// Create settings stored in memory.
+    pub fn memory() -> Self {
+        Self::new(MemoryStore::new())
+    }
+
+    /// Get the value of `key`, or `None` if it is not set or has another type.
+    pub fn get<T: FromVariant>(&self, key: &str) -> Option<T> {
+        let value = self.data.store.read(key)?;
+        let _ = self.data.values.borrow_mut().insert(key.to_string(), value.clone());
+        let result = value.get();
+        if result.is_none() {
+            warn!("The setting {} has the type {} instead of {}", key, value.type_(), T::static_variant_type());
+        }
+        result
+    }
+
+    /// Set the value of `key`, which is only written when it changed.
+    pub fn set<T: ToVariant>(&self, key: &str, value: &T) {
+        let value = value.to_variant();
+        if self.data.values.borrow().get(key) == Some(&value) {
+            return;
+        }
+        let _ = self.data.values.borrow_mut().insert(key.to_string(), value.clone());
+        self.data.store.write(key, &value);
+    }
+
+    /// Send the message returned by `callback` with the new value of `key` to `stream` when it is
+    /// changed outside of these settings, like by another process.
+    pub fn connect_changed<T, MSG, CALLBACK>(&self, key: &str, stream: &StreamHandle<MSG>, callback: CALLBACK)
+        where CALLBACK: Fn(T) -> MSG + 'static,
+              MSG: 'static,
+              T: FromVariant,
+    {
+        let stream = stream.clone();
+        let key_name = key.to_string();
+        let watcher = move |value: &Variant| {
+            match value.get() {
+                Some(value) => stream.emit(callback(value)),
+                None => warn!("The setting {} has the type {} instead of {}", key_name, value.type_(),
+                    T::static_variant_type()),
+            }
+        };
+        self.data.watchers.borrow_mut().entry(key.to_string()).or_default().push(Rc::new(watcher));
+    }
+}
@@ -0,0 +1,400 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Builders to show the standard GTK+ dialogs and send their outcome as a message.
+
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
+
+use gtk::{
+    ButtonsType,
+    ColorChooserDialog,
+    FileChooserAction,
+    FileChooserDialog,
+    FileFilter,
+    FontChooserDialog,
+    MessageType,
+    ResponseType,
+};
+use gtk::gdk::RGBA;
+use gtk::prelude::{
+    Cast,
+    ColorChooserExt,
+    DialogExt,
+    FileChooserExt,
+    FontChooserExt,
+    GtkWindowExt,
+    MessageDialogExt,
+    WidgetExt,
+    WidgetExtManual,
+};
+use glib::IsA;
+
+use crate::StreamHandle;
+
+#[derive(Default)]
+struct Options {
+    modal: bool,
+    parent: Option<gtk::Window>,
+    title: Option<String>,
+}
+
+impl Options {
+    fn apply<WINDOW: IsA<gtk::Window>>(&self, window: &WINDOW) {
+        window.set_modal(self.modal);
+        window.set_transient_for(self.parent.as_ref());
+        if let Some(ref title) = self.title {
+            window.set_title(title);
+        }
+    }
+}
+
+macro_rules! options_methods {
+    () => {
+        /// Block the input to the other windows while the dialog is shown.
+        pub fn modal(mut self, modal: bool) -> Self {
+            self.options.modal = modal;
+            self
+        }
+
+        /// Show the dialog on top of `parent`.
+        pub fn parent<WINDOW: IsA<gtk::Window>>(mut self, parent: &WINDOW) -> Self {
+            self.options.parent = Some(parent.clone().upcast());
+            self
+        }
+
+        /// Set the title of the dialog.
+        pub fn title(mut self, title: &str) -> Self {
+            self.options.title = Some(title.to_string());
+            self
+        }
+    };
+}
+
+// Show the dialog and send the message created from its outcome to `stream` when it gives a
+// response, after which it is destroyed.
+fn show<DIALOG, MSG, OUTPUT, OUTPUTFN, CALLBACK>(dialog: DIALOG, options: &Options, stream: &StreamHandle<MSG>,
+    output: OUTPUTFN, callback: CALLBACK)
+    where CALLBACK: FnOnce(OUTPUT) -> MSG + 'static,
+          DIALOG: IsA<gtk::Dialog> + IsA<gtk::Window> + IsA<gtk::Widget>,
+          MSG: 'static,
+          OUTPUTFN: Fn(&DIALOG, ResponseType) -> OUTPUT + 'static,
+{
+    options.apply(&dialog);
+    let stream = stream.clone();
+    let callback = Cell::new(Some(callback));
+    let _ = dialog.connect_response(move |dialog, response| {
+        if let Some(callback) = callback.take() {
+            stream.emit(callback(output(dialog, response)));
+        }
+        // Safety: the dialog is not used after being destroyed.
+        unsafe {
+            dialog.destroy();
+        }
+    });
+    dialog.show();
+}
+
+/// A dialog to choose files or folders.
+///
+/// ```ignore
+/// FileDialog::open()
+///     .parent(&self.root())
+///     .title("Open a file")
+///     .show(self.model.relm.stream(), Msg::Open);
+/// ```
+pub struct FileDialog {
+    action: FileChooserAction,
+    current_folder: Option<PathBuf>,
+    current_name: Option<String>,
+    filters: Vec<FileFilter>,
+    options: Options,
+}
+
+impl FileDialog {
+    fn new(action: FileChooserAction) -> Self {
+        FileDialog {
+            action,
+            current_folder: None,
+            current_name: None,
+            filters: vec![],
+            options: Options::default(),
+        }
+    }
+
+    /// Create a dialog to choose existing files.
+    pub fn open() -> Self {
+        Self::new(FileChooserAction::Open)
+    }
+
+    /// Create a dialog to choose the file to save to, which asks to confirm the overwrite of an
+    /// existing file.
+    pub fn save() -> Self {
+        Self::new(FileChooserAction::Save)
+    }
+
+    /// Create a dialog to choose existing folders.
+    pub fn select_folder() -> Self {
+        Self::new(FileChooserAction::SelectFolder)
+    }
+
+    options_methods!();
+
+    /// Set the folder shown when the dialog opens.
+    pub fn current_folder<P: AsRef<Path>>(mut self, folder: P) -> Self {
+        self.current_folder = Some(folder.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the suggested file name of a save dialog.
+    pub fn current_name(mut self, name: &str) -> Self {
+        self.current_name = Some(name.to_string());
+        self
+    }
+
+    /// Add a filter the user can select to restrict the files shown.
+    pub fn filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    fn build(&self, select_multiple: bool) -> FileChooserDialog {
+        let accept_label =
+            match self.action {
+                FileChooserAction::Save => "_Save",
+                FileChooserAction::SelectFolder => "_Select",
+                _ => "_Open",
+            };
+        let dialog = FileChooserDialog::with_buttons(None, None::<&gtk::Window>, self.action,
+            &[("_Cancel", ResponseType::Cancel), (accept_label, ResponseType::Accept)]);
+        dialog.set_default_response(ResponseType::Accept);
+        dialog.set_do_overwrite_confirmation(true);
+        dialog.set_select_multiple(select_multiple);
+        for filter in &self.filters {
+            dialog.add_filter(filter);
+        }
+        if let Some(ref folder) = self.current_folder {
+            let _ = dialog.set_current_folder(folder);
+        }
+        if let Some(ref name) = self.current_name {
+            dialog.set_current_name(name);
+        }
+        dialog
+    }
+
+    /// Show the dialog and send the message returned by `callback` with the chosen file, or
+    /// `None` if the dialog is cancelled.
+    pub fn show<MSG, CALLBACK>(self, stream: &StreamHandle<MSG>, callback: CALLBACK)
+        where CALLBACK: FnOnce(Option<PathBuf>) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let dialog = self.build(false);
+        show(dialog, &self.options, stream, |dialog, response| {
+            if response == ResponseType::Accept {
+                dialog.filename()
+            }
+            else {
+                None
+            }
+        }, callback);
+    }
+
+    /// Show the dialog and send the message returned by `callback` with the chosen files, which
+    /// are empty if the dialog is cancelled.
+    pub fn show_multiple<MSG, CALLBACK>(self, stream: &StreamHandle<MSG>, callback: CALLBACK)
+        where CALLBACK: FnOnce(Vec<PathBuf>) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let dialog = self.build(true);
+        show(dialog, &self.options, stream, |dialog, response| {
+            if response == ResponseType::Accept {
+                dialog.filenames()
+            }
+            else {
+                vec![]
+            }
+        }, callback);
+    }
+}
+
+/// A dialog showing a message, which gives the response of the button clicked by the user.
+///
+/// ```ignore
+/// MessageBox::new(MessageType::Question, ButtonsType::YesNo, "Delete the file?")
+///     .parent(&self.root())
+///     .modal(true)
+///     .show(self.model.relm.stream(), Msg::Delete);
+/// ```
+pub struct MessageBox {
+    buttons: ButtonsType,
+    extra_buttons: Vec<(String, ResponseType)>,
+    message_type: MessageType,
+    options: Options,
+    secondary_text: Option<String>,
+    text: String,
+}
+
+impl MessageBox {
+    /// Create a message dialog with the standard `buttons`.
+    pub fn new(message_type: MessageType, buttons: ButtonsType, text: &str) -> Self {
+        MessageBox {
+            buttons,
+            extra_buttons: vec![],
+            message_type,
+            options: Options::default(),
+            secondary_text: None,
+            text: text.to_string(),
+        }
+    }
+
+    options_methods!();
+
+    /// Add a button giving `response` after the standard buttons.
+    pub fn button(mut self, label: &str, response: ResponseType) -> Self {
+        self.extra_buttons.push((label.to_string(), response));
+        self
+    }
+
+    /// Set the text shown below the message.
+    pub fn secondary_text(mut self, text: &str) -> Self {
+        self.secondary_text = Some(text.to_string());
+        self
+    }
+
+    /// Show the dialog and send the message returned by `callback` with the response of the
+    /// dialog, which is `ResponseType::DeleteEvent` if it is closed without clicking a button.
+    pub fn show<MSG, CALLBACK>(self, stream: &StreamHandle<MSG>, callback: CALLBACK)
+        where CALLBACK: FnOnce(ResponseType) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let dialog = gtk::MessageDialog::new(None::<&gtk::Window>, gtk::DialogFlags::empty(), self.message_type,
+            self.buttons, &self.text);
+        dialog.set_secondary_text(self.secondary_text.as_deref());
+        for (label, response) in &self.extra_buttons {
+            let _ = dialog.add_button(label, *response);
+        }
+        show(dialog, &self.options, stream, |_, response| response, callback);
+    }
+}
+
+/// A dialog to choose a color.
+///
+/// ```ignore
+/// ColorDialog::new()
+///     .rgba(&self.model.color)
+///     .show(self.model.relm.stream(), Msg::ChangeColor);
+/// ```
+#[derive(Default)]
+pub struct ColorDialog {
+    options: Options,
+    rgba: Option<RGBA>,
+    use_alpha: bool,
+}
+
+impl ColorDialog {
+    /// Create a color dialog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    options_methods!();
+
+    /// Set the color selected when the dialog opens.
+    pub fn rgba(mut self, rgba: &RGBA) -> Self {
+        self.rgba = Some(*rgba);
+        self
+    }
+
+    /// Allow the user to choose the opacity of the color.
+    pub fn use_alpha(mut self, use_alpha: bool) -> Self {
+        self.use_alpha = use_alpha;
+        self
+    }
+
+    /// Show the dialog and send the message returned by `callback` with the chosen color, or
+    /// `None` if the dialog is cancelled.
+    pub fn show<MSG, CALLBACK>(self, stream: &StreamHandle<MSG>, callback: CALLBACK)
+        where CALLBACK: FnOnce(Option<RGBA>) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let dialog = ColorChooserDialog::new(None, None::<&gtk::Window>);
+        dialog.set_use_alpha(self.use_alpha);
+        if let Some(ref rgba) = self.rgba {
+            dialog.set_rgba(rgba);
+        }
+        show(dialog, &self.options, stream, |dialog, response| {
+            if response == ResponseType::Ok {
+                Some(dialog.rgba())
+            }
+            else {
+                None
+            }
+        }, callback);
+    }
+}
+
+/// A dialog to choose a font.
+///
+/// ```ignore
+/// FontDialog::new()
+///     .font("Monospace 12")
+///     .show(self.model.relm.stream(), Msg::ChangeFont);
+/// ```
+#[derive(Default)]
+pub struct FontDialog {
+    font: Option<String>,
+    options: Options,
+}
+
+impl FontDialog {
+    /// Create a font dialog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    options_methods!();
+
+    /// Set the font, like `"Sans Bold 12"`, selected when the dialog opens.
+    pub fn font(mut self, font: &str) -> Self {
+        self.font = Some(font.to_string());
+        self
+    }
+
+    /// Show the dialog and send the message returned by `callback` with the name of the chosen
+    /// font, or `None` if the dialog is cancelled.
+    pub fn show<MSG, CALLBACK>(self, stream: &StreamHandle<MSG>, callback: CALLBACK)
+        where CALLBACK: FnOnce(Option<String>) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let dialog = FontChooserDialog::new(None, None::<&gtk::Window>);
+        if let Some(ref font) = self.font {
+            dialog.set_font(font);
+        }
+        show(dialog, &self.options, stream, |dialog, response| {
+            if response == ResponseType::Ok {
+                dialog.font().map(|font| font.to_string())
+            }
+            else {
+                None
+            }
+        }, callback);
+    }
+}
@@ -35,20 +35,29 @@
 mod into;
 mod macros;
 
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
 use std::time::SystemTime;
 
 pub use crate::core::{EventStream, StreamHandle};
 
 pub use self::into::{IntoOption, IntoPair};
 
+type ComponentHandle<UPDATE> = Rc<RefCell<Weak<RefCell<UPDATE>>>>;
+type PendingCallbacks<UPDATE> = Rc<RefCell<Vec<Box<dyn FnOnce(&mut UPDATE)>>>>;
+
 /// Handle event stream to send messages to the [`update()`](trait.Update.html#tymethod.update) method.
 pub struct Relm<UPDATE: Update> {
+    component: ComponentHandle<UPDATE>,
+    pending: PendingCallbacks<UPDATE>,
     stream: StreamHandle<UPDATE::Msg>,
 }
 
 impl<UPDATE: Update> Clone for Relm<UPDATE> {
     fn clone(&self) -> Self {
         Relm {
+            component: self.component.clone(),
+            pending: self.pending.clone(),
             stream: self.stream.clone(),
         }
     }
@@ -58,6 +67,8 @@ impl<UPDATE: Update> Relm<UPDATE> {
     /// Create a new relm stream handler.
     pub fn new(stream: &EventStream<UPDATE::Msg>) -> Self {
         Relm {
+            component: Rc::new(RefCell::new(Weak::new())),
+            pending: Rc::new(RefCell::new(vec![])),
             stream: stream.downgrade(),
         }
     }
@@ -67,6 +78,33 @@ impl<UPDATE: Update> Relm<UPDATE> {
     pub fn stream(&self) -> &StreamHandle<UPDATE::Msg> {
         &self.stream
     }
+
+    /// Quit the innermost main loop started by [`run()`](fn.run.html) with `value`.
+    /// This is the same as [`relm::quit()`](fn.quit.html).
+    pub fn quit<VALUE: 'static>(&self, value: VALUE) {
+        crate::main_loop::quit(value);
+    }
+
+    /// Get the component, if it is initialized and not dropped.
+    pub(crate) fn component(&self) -> Option<Rc<RefCell<UPDATE>>> {
+        self.component.borrow().upgrade()
+    }
+
+    /// Call `callback` with the component, outside of the processing of a message.
+    ///
+    /// When the component is being updated, for instance while `update()` runs a nested main
+    /// loop, `callback` is called once the current message is processed.
+    /// It is dropped when the component is not initialized yet or was dropped.
+    pub(crate) fn with_component<CALLBACK>(&self, callback: CALLBACK)
+        where CALLBACK: FnOnce(&mut UPDATE) + 'static,
+    {
+        if let Some(component) = self.component() {
+            match component.try_borrow_mut() {
+                Ok(mut component) => callback(&mut component),
+                Err(_) => self.pending.borrow_mut().push(Box::new(callback)),
+            }
+        }
+    }
 }
 
 /// Trait for a basic (non-widget) component.
@@ -93,6 +131,11 @@ pub trait Update
 
     /// Method called when a message is received from an event.
     fn update(&mut self, event: Self::Msg);
+
+    /// Give the model when the component is dropped at the end of [`run()`](fn.run.html).
+    fn into_model(self) -> Option<Self::Model> {
+        None
+    }
 }
 
 /// Trait for an `Update` object that can be created directly.
@@ -141,8 +184,16 @@ pub fn init_component<UPDATE>(stream: &EventStream<UPDATE::Msg>, mut component:
           UPDATE::Msg: DisplayVariant + 'static,
 {
     component.subscriptions(relm);
+    let component = Rc::new(RefCell::new(component));
+    *relm.component.borrow_mut() = Rc::downgrade(&component);
+    let pending = relm.pending.clone();
     stream.set_callback(move |event| {
-        update_component(&mut component, event);
+        let mut component = component.borrow_mut();
+        update_component(&mut *component, event);
+        let callbacks: Vec<_> = pending.borrow_mut().drain(..).collect();
+        for callback in callbacks {
+            callback(&mut component);
+        }
     });
 }
 
@@ -20,8 +20,9 @@
  */
 
 use glib::{IsA, Object};
+use gtk::gio;
 
-use super::{Relm, run};
+use super::{Exit, Relm, run};
 use crate::state::Update;
 
 /// Trait to implement to manage widget's events.
@@ -38,6 +39,16 @@ pub trait Widget
     fn init_view(&mut self) {
     }
 
+    /// Finish the initialization of the widget, after the view is created.
+    /// This is the second step of the initialization: unlike `model()`, this method has access to
+    /// the widgets, so it can store in the model what is derived from them and start the work that
+    /// depends on both.
+    /// The returned messages are emitted right away, so they are processed by the
+    /// [`update()`](trait.Update.html#tymethod.update) method as soon as the main loop runs.
+    fn post_init(&mut self, _relm: &Relm<Self>) -> Vec<Self::Msg> {
+        vec![]
+    }
+
     /// Method called when the widget is added to its parent.
     /// This is currently only used to set the child properties of a widget as relm widget could
     /// have child properties and we don't know its parent when it is defined. Thus, we call
@@ -45,6 +56,19 @@ pub trait Widget
     fn on_add<W: IsA<gtk::Widget> + IsA<Object>>(&self, _parent: W) {
     }
 
+    /// Get the message to send to the root widget of [`run_application()`](fn.run_application.html)
+    /// when the application is asked to open `files`, with the `HANDLES_OPEN` flag.
+    fn open(_files: Vec<gio::File>, _hint: String) -> Option<Self::Msg> {
+        None
+    }
+
+    /// Get the message to send to the root widget of [`run_application()`](fn.run_application.html)
+    /// when the application receives a command line, with the `HANDLES_COMMAND_LINE` flag.
+    /// The exit status of the application can be set here with `set_exit_status()`.
+    fn command_line(_command_line: gio::ApplicationCommandLine) -> Option<Self::Msg> {
+        None
+    }
+
     /// Get the parent ID.
     /// This is useful for custom Container implementation: when you implement the
     /// [`Container::add_widget()`](trait.Container.html#tymethod.add_widget), you might want to
@@ -59,8 +83,9 @@ pub trait Widget
     /// Get the root widget of the view.
     fn root(&self) -> Self::Root;
 
-    /// Create the window from this widget and start the main loop.
-    fn run(model_param: Self::ModelParam) -> Result<(), glib::BoolError>
+    /// Create the window from this widget and start the main loop, until [`quit()`](fn.quit.html)
+    /// is called.
+    fn run(model_param: Self::ModelParam) -> Result<Exit<Self::Model>, glib::BoolError>
         where Self: 'static,
     {
         run::<Self>(model_param)
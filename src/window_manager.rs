@@ -0,0 +1,213 @@
+/*
+ * Copyright (c) 2026 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Track the toplevel windows of an application to quit when they are closed.
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use gtk::prelude::{Cast, GtkWindowExt, ObjectExt, WidgetExt, WidgetExtManual};
+use glib::{IsA, WeakRef};
+
+use crate::{Component, StreamHandle, Widget, init};
+
+/// The identifier of a window tracked by a [`WindowManager`](struct.WindowManager.html).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct WindowId(u64);
+
+/// The events of the windows, sent as messages to the component owning the
+/// [`WindowManager`](struct.WindowManager.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WindowEvent {
+    /// A window is tracked.
+    Opened(WindowId),
+    /// A tracked window was destroyed.
+    Closed(WindowId),
+}
+
+/// When the [`WindowManager`](struct.WindowManager.html) quits the main loop.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QuitPolicy {
+    /// Quit when the last tracked window is closed.
+    LastWindowClosed,
+    /// Quit when the first tracked window, usually the main window, is closed, destroying the
+    /// other windows.
+    MainWindowClosed,
+    /// Never quit: the component decides when to quit with the `WindowEvent::Closed` messages.
+    Manual,
+}
+
+struct ManagedWindow {
+    // Keep the component of the window alive until it is destroyed.
+    component: Option<Box<dyn Any>>,
+    id: WindowId,
+    window: WeakRef<gtk::Window>,
+}
+
+struct Windows<MSG> {
+    callback: Box<dyn Fn(WindowEvent) -> MSG>,
+    // The first tracked window.
+    main_window: Cell<Option<WindowId>>,
+    next_id: Cell<u64>,
+    policy: QuitPolicy,
+    stream: StreamHandle<MSG>,
+    windows: RefCell<Vec<ManagedWindow>>,
+}
+
+/// Open windows as components and track them, to quit the main loop started by
+/// [`run()`](fn.run.html) according to a [`QuitPolicy`](enum.QuitPolicy.html).
+///
+/// The events of the windows are sent to `stream` as the messages returned by the callback given
+/// to [`new()`](#method.new).
+/// A clone of the manager can be given to other components, so that they open windows too.
+pub struct WindowManager<MSG> {
+    windows: Rc<Windows<MSG>>,
+}
+
+impl<MSG> Clone for WindowManager<MSG> {
+    fn clone(&self) -> Self {
+        WindowManager {
+            windows: self.windows.clone(),
+        }
+    }
+}
+
+impl<MSG: 'static> WindowManager<MSG> {
+    /// Create a window manager sending the events of the windows to `stream`.
+    pub fn new<CALLBACK>(stream: StreamHandle<MSG>, policy: QuitPolicy, callback: CALLBACK) -> Self
+        where CALLBACK: Fn(WindowEvent) -> MSG + 'static,
+    {
+        WindowManager {
+            windows: Rc::new(Windows {
+                callback: Box::new(callback),
+                main_window: Cell::new(None),
+                next_id: Cell::new(0),
+                policy,
+                stream,
+                windows: RefCell::new(vec![]),
+            }),
+        }
+    }
+
+    /// Track an existing window, like the one of the root component.
+    pub fn add<WINDOW: IsA<gtk::Window>>(&self, window: &WINDOW) -> WindowId {
+        self.track(window.upcast_ref(), None)
+    }
+
+    /// Close the window `id`.
+    pub fn close(&self, id: WindowId) {
+        if let Some(window) = self.window(id) {
+            window.close();
+        }
+    }
+
+    /// Create the component `WIDGET` and track its window, until it is destroyed.
+    pub fn open<WIDGET>(&self, model_param: WIDGET::ModelParam) -> Result<WindowId, glib::BoolError>
+        where WIDGET: Widget + 'static,
+              WIDGET::Root: IsA<gtk::Window>,
+    {
+        let component = init::<WIDGET>(model_param)?;
+        let window = component.widget().clone().upcast();
+        Ok(self.track(&window, Some(Box::new(component))))
+    }
+
+    /// Get the stream of the component of the window `id`, if it was opened as a `WIDGET`.
+    pub fn stream<WIDGET: Widget + 'static>(&self, id: WindowId) -> Option<StreamHandle<WIDGET::Msg>> {
+        self.windows.windows.borrow().iter()
+            .find(|window| window.id == id)
+            .and_then(|window| window.component.as_ref())
+            .and_then(|component| component.downcast_ref::<Component<WIDGET>>())
+            .map(Component::stream)
+    }
+
+    /// Get the window `id`, if it is not destroyed.
+    pub fn window(&self, id: WindowId) -> Option<gtk::Window> {
+        self.windows.windows.borrow().iter()
+            .find(|window| window.id == id)
+            .and_then(|window| window.window.upgrade())
+    }
+
+    /// Get the tracked windows, in the order they were opened.
+    pub fn windows(&self) -> Vec<WindowId> {
+        self.windows.windows.borrow().iter()
+            .map(|window| window.id)
+            .collect()
+    }
+
+    fn track(&self, window: &gtk::Window, component: Option<Box<dyn Any>>) -> WindowId {
+        let id = WindowId(self.windows.next_id.get());
+        self.windows.next_id.set(id.0 + 1);
+        if self.windows.main_window.get().is_none() {
+            self.windows.main_window.set(Some(id));
+        }
+        self.windows.windows.borrow_mut().push(ManagedWindow {
+            component,
+            id,
+            window: window.downgrade(),
+        });
+        let windows = Rc::downgrade(&self.windows);
+        window.connect_destroy(move |_| closed(&windows, id));
+        self.windows.stream.emit((self.windows.callback)(WindowEvent::Opened(id)));
+        id
+    }
+}
+
+fn closed<MSG: 'static>(windows: &Weak<Windows<MSG>>, id: WindowId) {
+    let windows =
+        match windows.upgrade() {
+            Some(windows) => windows,
+            None => return,
+        };
+    let (closed_window, remaining) = {
+        let mut managed_windows = windows.windows.borrow_mut();
+        let closed_window = managed_windows.iter()
+            .position(|window| window.id == id)
+            .map(|index| managed_windows.remove(index));
+        (closed_window, managed_windows.len())
+    };
+    let closed_window =
+        match closed_window {
+            Some(closed_window) => closed_window,
+            None => return,
+        };
+    if let Some(component) = closed_window.component {
+        // The component may be processing a message which closed its window.
+        glib::idle_add_local_once(move || drop(component));
+    }
+    windows.stream.emit((windows.callback)(WindowEvent::Closed(id)));
+    match windows.policy {
+        QuitPolicy::LastWindowClosed if remaining == 0 => crate::quit(()),
+        QuitPolicy::MainWindowClosed if windows.main_window.get() == Some(id) => {
+            let other_windows: Vec<_> = windows.windows.borrow().iter()
+                .filter_map(|window| window.window.upgrade())
+                .collect();
+            for window in other_windows {
+                // Safety: the components of the windows are dropped later, when idle.
+                unsafe {
+                    window.destroy();
+                }
+            }
+            crate::quit(());
+        },
+        _ => (),
+    }
+}